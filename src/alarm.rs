@@ -0,0 +1,184 @@
+use crate::MeasurementBundle;
+
+/// Severity of a concentration compared to the thresholds of an [`AlarmConfig`].
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AlarmLevel {
+    /// Below the warning threshold.
+    #[default]
+    Ok,
+    /// At or above the warning threshold, but below the alarm threshold.
+    Warning,
+    /// At or above the alarm threshold.
+    Alarm,
+}
+
+/// Margins a concentration has to fall below a threshold before a raised level is cleared.
+///
+/// Without hysteresis, the default, a reading fluctuating around a threshold toggles the level
+/// on every call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HysteresisConfig {
+    pub eco2_hysteresis_ppm: u16,
+    pub tvoc_hysteresis_ppb: u16,
+}
+
+/// Warning and alarm thresholds for eCO2 and TVOC.
+///
+/// The default warns from 1000 ppm eCO2 and 660 ppb TVOC and alarms from 2000 ppm eCO2 and
+/// 2200 ppb TVOC, without hysteresis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlarmConfig {
+    pub eco2_warn_ppm: u16,
+    pub eco2_alarm_ppm: u16,
+    pub tvoc_warn_ppb: u16,
+    pub tvoc_alarm_ppb: u16,
+    pub hysteresis: HysteresisConfig,
+}
+
+impl AlarmConfig {
+    /// Rates the eCO2 and TVOC concentrations of a measurement, returned in this order.
+    ///
+    /// The hysteresis is not applied, see [`AlarmConfig::check_alarms_with_previous()`].
+    pub fn check_alarms(&self, bundle: &MeasurementBundle) -> (AlarmLevel, AlarmLevel) {
+        self.check_alarms_with_previous(bundle, (AlarmLevel::Ok, AlarmLevel::Ok))
+    }
+
+    /// Rates the eCO2 and TVOC concentrations of a measurement like
+    /// [`AlarmConfig::check_alarms()`], but keeps each `previous` level until the concentration
+    /// falls the hysteresis margin below its threshold.
+    ///
+    /// For example, with an alarm threshold of 2000 ppm and a hysteresis of 100 ppm an eCO2
+    /// alarm stays raised down to 1900 ppm.
+    pub fn check_alarms_with_previous(
+        &self,
+        bundle: &MeasurementBundle,
+        previous: (AlarmLevel, AlarmLevel),
+    ) -> (AlarmLevel, AlarmLevel) {
+        (
+            level_with_hysteresis(
+                bundle.eco2.ppm(),
+                self.eco2_warn_ppm,
+                self.eco2_alarm_ppm,
+                self.hysteresis.eco2_hysteresis_ppm,
+                previous.0,
+            ),
+            level_with_hysteresis(
+                bundle.tvoc.ppb(),
+                self.tvoc_warn_ppb,
+                self.tvoc_alarm_ppb,
+                self.hysteresis.tvoc_hysteresis_ppb,
+                previous.1,
+            ),
+        )
+    }
+}
+
+impl Default for AlarmConfig {
+    fn default() -> Self {
+        Self {
+            eco2_warn_ppm: 1000,
+            eco2_alarm_ppm: 2000,
+            tvoc_warn_ppb: 660,
+            tvoc_alarm_ppb: 2200,
+            hysteresis: HysteresisConfig::default(),
+        }
+    }
+}
+
+fn level_with_hysteresis(
+    value: u16,
+    warn: u16,
+    alarm: u16,
+    hysteresis: u16,
+    previous: AlarmLevel,
+) -> AlarmLevel {
+    // A level reached with the value raised by the margin is kept, but never raised above the
+    // previous level.
+    let held = level(value.saturating_add(hysteresis), warn, alarm).min(previous);
+    level(value, warn, alarm).max(held)
+}
+
+fn level(value: u16, warn: u16, alarm: u16) -> AlarmLevel {
+    if value >= alarm {
+        AlarmLevel::Alarm
+    } else if value >= warn {
+        AlarmLevel::Warning
+    } else {
+        AlarmLevel::Ok
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{AlarmConfig, AlarmLevel, HysteresisConfig};
+    use crate::bundle;
+
+    #[test]
+    fn test_check_alarms() {
+        let config = AlarmConfig::default();
+        let expected = [
+            ((400, 0), (AlarmLevel::Ok, AlarmLevel::Ok)),
+            ((999, 659), (AlarmLevel::Ok, AlarmLevel::Ok)),
+            ((1000, 660), (AlarmLevel::Warning, AlarmLevel::Warning)),
+            ((1999, 100), (AlarmLevel::Warning, AlarmLevel::Ok)),
+            ((2000, 2199), (AlarmLevel::Alarm, AlarmLevel::Warning)),
+            ((500, 2200), (AlarmLevel::Ok, AlarmLevel::Alarm)),
+        ];
+        for ((eco2, tvoc), levels) in expected {
+            assert_eq!(config.check_alarms(&bundle(eco2, tvoc)), levels);
+        }
+        assert!(AlarmLevel::Alarm > AlarmLevel::Warning);
+    }
+
+    #[test]
+    fn test_hysteresis() {
+        let config = AlarmConfig {
+            hysteresis: HysteresisConfig {
+                eco2_hysteresis_ppm: 100,
+                tvoc_hysteresis_ppb: 50,
+            },
+            ..AlarmConfig::default()
+        };
+
+        let mut levels = (AlarmLevel::Ok, AlarmLevel::Ok);
+        let mut eco2_levels = [AlarmLevel::Ok; 6];
+        for (i, eco2) in [2010, 1950, 2020, 1920, 1990, 1899].into_iter().enumerate() {
+            levels = config.check_alarms_with_previous(&bundle(eco2, 0), levels);
+            eco2_levels[i] = levels.0;
+        }
+        assert_eq!(
+            eco2_levels,
+            [
+                AlarmLevel::Alarm,
+                AlarmLevel::Alarm,
+                AlarmLevel::Alarm,
+                AlarmLevel::Alarm,
+                AlarmLevel::Alarm,
+                AlarmLevel::Warning,
+            ]
+        );
+        assert_eq!(levels.1, AlarmLevel::Ok);
+
+        let levels = config.check_alarms_with_previous(&bundle(899, 0), levels);
+        assert_eq!(levels.0, AlarmLevel::Ok);
+
+        let levels = config.check_alarms_with_previous(&bundle(400, 700), levels);
+        assert_eq!(levels.1, AlarmLevel::Warning);
+        let levels = config.check_alarms_with_previous(&bundle(400, 620), levels);
+        assert_eq!(levels.1, AlarmLevel::Warning);
+        let levels = config.check_alarms_with_previous(&bundle(400, 609), levels);
+        assert_eq!(levels.1, AlarmLevel::Ok);
+
+        let from_ok =
+            config.check_alarms_with_previous(&bundle(1950, 0), (AlarmLevel::Ok, AlarmLevel::Ok));
+        assert_eq!(from_ok.0, AlarmLevel::Warning);
+        assert_eq!(config.check_alarms(&bundle(1950, 0)).0, AlarmLevel::Warning);
+    }
+}
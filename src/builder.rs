@@ -0,0 +1,264 @@
+#[cfg(feature = "blocking")]
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs;
+
+use crate::error::Error;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::interface::Interface;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::{mode, ENS160_RESET_DELAY_MS};
+use crate::{Ens160, InterruptConfig};
+
+/// Configures the sensor and starts the measurements.
+///
+/// Created with [`Ens160::configure()`]. [`Ens160Builder::build()`] performs the startup
+/// sequence: reset, idle, clear the command register, write the compensation values and the
+/// interrupt configuration and switch to operational mode.
+#[derive(Debug)]
+pub struct Ens160Builder<I2C> {
+    ens160: Ens160<I2C>,
+    config: StartupConfig,
+}
+
+/// Configuration written during the startup sequence, see [`Ens160::configure_and_start()`].
+///
+/// Values which are `None` are left at the defaults of the sensor.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StartupConfig {
+    /// Temperature used for compensation, see [`Ens160::set_temp()`].
+    pub temp_scaled: Option<i16>,
+    /// Relative humidity used for compensation, see [`Ens160::set_hum()`].
+    pub hum_scaled: Option<u16>,
+    /// Interrupt configuration, see [`Ens160::set_interrupt_config()`].
+    pub interrupt: Option<InterruptConfig>,
+}
+
+impl<I2C, M> Ens160<I2C, M> {
+    /// Starts configuring the sensor with a [`Ens160Builder`].
+    pub fn configure(self) -> Ens160Builder<I2C> {
+        Ens160Builder {
+            ens160: self.into_unknown(),
+            config: StartupConfig::default(),
+        }
+    }
+}
+
+impl<I2C> Ens160Builder<I2C> {
+    /// Sets the temperature used for compensation, see [`Ens160::set_temp()`].
+    pub fn with_temp(mut self, ambient_temp: i16) -> Self {
+        self.config.temp_scaled = Some(ambient_temp);
+        self
+    }
+
+    /// Sets the relative humidity used for compensation, see [`Ens160::set_hum()`].
+    pub fn with_hum(mut self, relative_humidity: u16) -> Self {
+        self.config.hum_scaled = Some(relative_humidity);
+        self
+    }
+
+    /// Sets the interrupt configuration, see [`Ens160::set_interrupt_config()`].
+    pub fn with_interrupt(mut self, config: InterruptConfig) -> Self {
+        self.config.interrupt = Some(config);
+        self
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<I2C, E> Ens160Builder<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Performs the startup sequence and returns the sensor in operational mode.
+    ///
+    /// `delay` is used to wait for the sensor after the reset.
+    pub fn build<D: DelayNs>(self, delay: &mut D) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        let ens160 = self.ens160.reset()?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS);
+        let mut ens160 = ens160.idle()?;
+        ens160.clear_command()?;
+        match (self.config.temp_scaled, self.config.hum_scaled) {
+            (Some(temp), Some(hum)) => ens160.set_temp_and_hum(temp, hum)?,
+            (Some(temp), None) => ens160.set_temp(temp)?,
+            (None, Some(hum)) => ens160.set_hum(hum)?,
+            (None, None) => {}
+        }
+        if let Some(config) = self.config.interrupt {
+            ens160.set_interrupt_config(config)?;
+        }
+        ens160.operational()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, E> Ens160Builder<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Performs the startup sequence and returns the sensor in operational mode.
+    ///
+    /// `delay` is used to wait for the sensor after the reset.
+    pub async fn build<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        let ens160 = self.ens160.reset().await?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS).await;
+        let mut ens160 = ens160.idle().await?;
+        ens160.clear_command().await?;
+        match (self.config.temp_scaled, self.config.hum_scaled) {
+            (Some(temp), Some(hum)) => ens160.set_temp_and_hum(temp, hum).await?,
+            (Some(temp), None) => ens160.set_temp(temp).await?,
+            (None, Some(hum)) => ens160.set_hum(hum).await?,
+            (None, None) => {}
+        }
+        if let Some(config) = self.config.interrupt {
+            ens160.set_interrupt_config(config).await?;
+        }
+        ens160.operational().await
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<I2C, M, E> Ens160<I2C, M>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Performs the startup sequence with `config` and returns the sensor in operational mode.
+    ///
+    /// Resets the sensor, waits for it with `delay`, switches to idle mode, clears the command
+    /// register, writes the values of `config` which are `Some` and switches to operational
+    /// mode. This is the same sequence as [`Ens160Builder::build()`].
+    pub fn configure_and_start<D: DelayNs>(
+        self,
+        config: StartupConfig,
+        delay: &mut D,
+    ) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        Ens160Builder {
+            ens160: self.into_unknown(),
+            config,
+        }
+        .build(delay)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, M, E> Ens160<I2C, M>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Performs the startup sequence with `config` and returns the sensor in operational mode.
+    ///
+    /// Resets the sensor, waits for it with `delay`, switches to idle mode, clears the command
+    /// register, writes the values of `config` which are `Some` and switches to operational
+    /// mode. This is the same sequence as [`Ens160Builder::build()`].
+    pub async fn configure_and_start<D: DelayNs>(
+        self,
+        config: StartupConfig,
+        delay: &mut D,
+    ) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        Ens160Builder {
+            ens160: self.into_unknown(),
+            config,
+        }
+        .build(delay)
+        .await
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod test {
+
+    use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use super::StartupConfig;
+    use crate::{Ens160, InterruptConfig, PinMode};
+
+    #[test]
+    fn test_build_sequence() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0xF0]),
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write(0x52, vec![0x12, 0xCC]),
+            Transaction::write(0x52, vec![0x13, 0x89, 0x4A, 0x00, 0x64]),
+            Transaction::write(0x52, vec![0x11, 0b00100011]),
+            Transaction::write(0x52, vec![0x10, 0x02]),
+        ];
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+        let config = InterruptConfig::default()
+            .enable_for_measure_data_is_ready()
+            .set_pin_mode(PinMode::PushPull);
+        let device = Ens160::new(Mock::new(&expectations), 0x52)
+            .configure()
+            .with_temp(2500)
+            .with_hum(5000)
+            .with_interrupt(config)
+            .build(&mut delay)
+            .unwrap();
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_build_without_configuration() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0xF0]),
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write(0x52, vec![0x12, 0xCC]),
+            Transaction::write(0x52, vec![0x10, 0x02]),
+        ];
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+        let device = Ens160::new(Mock::new(&expectations), 0x52)
+            .configure()
+            .build(&mut delay)
+            .unwrap();
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_configure_and_start() {
+        let startup = [
+            Transaction::write(0x52, vec![0x10, 0xF0]),
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write(0x52, vec![0x12, 0xCC]),
+        ];
+        let operational = Transaction::write(0x52, vec![0x10, 0x02]);
+        let configs = [
+            (StartupConfig::default(), vec![]),
+            (
+                StartupConfig {
+                    temp_scaled: Some(2500),
+                    ..Default::default()
+                },
+                vec![Transaction::write(0x52, vec![0x13, 0x89, 0x4A])],
+            ),
+            (
+                StartupConfig {
+                    hum_scaled: Some(5000),
+                    interrupt: Some(InterruptConfig::data_ready_open_drain_active_low()),
+                    ..Default::default()
+                },
+                vec![
+                    Transaction::write(0x52, vec![0x15, 0x00, 0x64]),
+                    Transaction::write(0x52, vec![0x11, 0b00000011]),
+                ],
+            ),
+        ];
+        for (config, writes) in configs {
+            let mut expectations = startup.to_vec();
+            expectations.extend(writes);
+            expectations.push(operational.clone());
+            let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+            let device = Ens160::new(Mock::new(&expectations), 0x52)
+                .configure_and_start(config, &mut delay)
+                .unwrap();
+            device.release().done();
+            delay.done();
+        }
+    }
+}
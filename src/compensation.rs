@@ -0,0 +1,12 @@
+/// A companion sensor providing the ambient temperature and humidity used to compensate the
+/// measurements of the `ENS160`.
+///
+/// Implement this for the temperature and humidity sensor of your board, e.g. a BME280 or an
+/// SHT4x, and pass it to [`Ens160::set_compensation_from()`](crate::Ens160::set_compensation_from).
+pub trait TemperatureHumiditySensor {
+    /// Returns the ambient temperature in °C scaled by 100, e.g. 2550 for 25.50 °C.
+    fn read_temp_scaled_100(&mut self) -> i16;
+
+    /// Returns the relative humidity in % scaled by 100, e.g. 5025 for 50.25% RH.
+    fn read_hum_scaled_100(&mut self) -> u16;
+}
@@ -1,90 +1,448 @@
+use core::fmt;
+use core::marker::PhantomData;
+
 use super::registers::*;
-use super::{AirQualityIndex, Command, ECo2, OperationMode, Status};
-use crate::InterruptConfig;
-#[cfg(feature = "blocking")]
-use embedded_hal::i2c::I2c;
-#[cfg(feature = "async")]
-use embedded_hal_async::i2c::{I2c, SevenBitAddress};
+use super::{
+    AirQualityIndex, Command, ECo2, FirmwareVersion, MeasurementBundle, OperationMode, SensorStats,
+    Status, Tvoc,
+};
+use crate::error::Error;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::interface::Interface;
+use crate::interface::SpiInterface;
+use crate::mode;
+use crate::{Address, InterruptConfig, TemperatureHumiditySensor};
+use crate::{AlarmConfig, AlarmLevel};
+#[cfg(feature = "fixed")]
+use fixed::types::{I12F4, U7F9};
 
-/// A driver for the `ENS160` sensor connected with I2C to the host.
-pub struct Ens160<I2C> {
+/// A driver for the `ENS160` sensor connected with I2C or SPI to the host.
+///
+/// The bus is accessed through the [`Interface`](crate::Interface) trait, which is implemented
+/// for every I2C bus and for SPI devices wrapped in [`SpiInterface`].
+///
+/// The operation mode of the sensor is tracked by `M`, see [`mode`]. Mode transitions
+/// consume the driver and return it in the new mode.
+pub struct Ens160<I2C, M = mode::Unknown> {
     i2c: I2C,
     address: u8,
+    ventilation_eco2: ECo2,
+    ventilation_tvoc: Tvoc,
+    alarm_config: Option<AlarmConfig>,
+    mode: PhantomData<M>,
+}
+
+/// Default eCO2 concentration above which [`Ens160::ventilation_needed()`] returns `true`.
+const DEFAULT_VENTILATION_ECO2_PPM: u16 = 1000;
+/// Default TVOC concentration above which [`Ens160::ventilation_needed()`] returns `true`.
+const DEFAULT_VENTILATION_TVOC_PPB: u16 = 660;
+
+impl<I2C: fmt::Debug, M> fmt::Debug for Ens160<I2C, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ens160")
+            .field("i2c", &self.i2c)
+            .field("address", &format_args!("0x{:02X}", self.address))
+            .field("ventilation_eco2", &self.ventilation_eco2)
+            .field("ventilation_tvoc", &self.ventilation_tvoc)
+            .field("alarm_config", &self.alarm_config)
+            .finish()
+    }
+}
+
+impl<I2C: Clone, M> Clone for Ens160<I2C, M> {
+    fn clone(&self) -> Self {
+        Self {
+            i2c: self.i2c.clone(),
+            address: self.address,
+            ventilation_eco2: self.ventilation_eco2,
+            ventilation_tvoc: self.ventilation_tvoc,
+            alarm_config: self.alarm_config,
+            mode: PhantomData,
+        }
+    }
 }
 
 impl<I2C> Ens160<I2C> {
     /// Creates a new sensor driver.
-    pub fn new(i2c: I2C, address: u8) -> Self {
-        Self { i2c, address }
+    ///
+    /// The operation mode of the sensor is unknown until the first transition.
+    pub const fn new(i2c: I2C, address: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            ventilation_eco2: ECo2::from_ppm(DEFAULT_VENTILATION_ECO2_PPM),
+            ventilation_tvoc: Tvoc::from_ppb(DEFAULT_VENTILATION_TVOC_PPB),
+            alarm_config: None,
+            mode: PhantomData,
+        }
+    }
+
+    /// Creates a new sensor driver for the given [`Address`].
+    pub fn new_with_address(i2c: I2C, address: Address) -> Self {
+        Self::new(i2c, address.into())
+    }
+
+    /// Creates a new sensor driver for a sensor with the ADDR pin pulled low.
+    pub fn new_primary(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, Address::Primary)
+    }
+
+    /// Creates a new sensor driver for a sensor with the ADDR pin pulled high.
+    pub fn new_secondary(i2c: I2C) -> Self {
+        Self::new_with_address(i2c, Address::Secondary)
     }
+}
 
+impl<I2C, M> Ens160<I2C, M> {
     /// Releases the underlying I2C bus and destroys the driver.
     pub fn release(self) -> I2C {
         self.i2c
     }
+
+    /// Returns the I2C address of the sensor, 0 for sensors connected with SPI.
+    pub fn address(&self) -> u8 {
+        self.address
+    }
+
+    /// Borrows the underlying I2C bus.
+    pub fn i2c(&self) -> &I2C {
+        &self.i2c
+    }
+
+    /// Mutably borrows the underlying I2C bus.
+    ///
+    /// The driver doesn't notice changes made through the bus. Writing to the sensor directly
+    /// can leave it in another operation mode than tracked by the driver.
+    pub fn i2c_mut(&mut self) -> &mut I2C {
+        &mut self.i2c
+    }
+
+    /// Sets the concentrations above which [`Ens160::ventilation_needed()`] returns `true`,
+    /// 1000 ppm eCO2 and 660 ppb TVOC by default.
+    pub fn set_ventilation_thresholds(&mut self, eco2_ppm: u16, tvoc_ppb: u16) {
+        self.ventilation_eco2 = ECo2::from(eco2_ppm);
+        self.ventilation_tvoc = Tvoc::from(tvoc_ppb);
+    }
+
+    /// Sets the thresholds used by [`Ens160::alarms_triggered()`].
+    pub fn with_alarm_config(mut self, config: AlarmConfig) -> Self {
+        self.alarm_config = Some(config);
+        self
+    }
+
+    /// Returns the thresholds set with [`Ens160::with_alarm_config()`].
+    pub fn alarm_config(&self) -> Option<AlarmConfig> {
+        self.alarm_config
+    }
+
+    /// Rates a valid measurement against the alarm thresholds, see
+    /// [`Ens160::alarms_triggered()`].
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn check_alarms(
+        &self,
+        bundle: &MeasurementBundle,
+        previous: (AlarmLevel, AlarmLevel),
+    ) -> (AlarmLevel, AlarmLevel) {
+        match self.alarm_config {
+            Some(config) if bundle.is_valid() => {
+                config.check_alarms_with_previous(bundle, previous)
+            }
+            _ => (AlarmLevel::Ok, AlarmLevel::Ok),
+        }
+    }
+
+    /// Returns `true` if a valid measurement exceeds one of the ventilation thresholds.
+    #[cfg(any(feature = "blocking", feature = "async"))]
+    fn exceeds_ventilation_thresholds(&self, bundle: &MeasurementBundle) -> bool {
+        bundle.is_valid()
+            && (bundle.eco2.value > self.ventilation_eco2
+                || bundle.tvoc.value > self.ventilation_tvoc)
+    }
+
+    /// Stops tracking the operation mode of the sensor.
+    pub fn into_unknown(self) -> Ens160<I2C, mode::Unknown> {
+        self.into_mode()
+    }
+
+    fn into_mode<N>(self) -> Ens160<I2C, N> {
+        Ens160 {
+            i2c: self.i2c,
+            address: self.address,
+            ventilation_eco2: self.ventilation_eco2,
+            ventilation_tvoc: self.ventilation_tvoc,
+            alarm_config: self.alarm_config,
+            mode: PhantomData,
+        }
+    }
+}
+
+impl<SPI> Ens160<SpiInterface<SPI>> {
+    /// Creates a new sensor driver for a sensor connected with SPI.
+    pub fn new_spi(spi: SPI) -> Self {
+        Self::new(SpiInterface::new(spi), 0)
+    }
 }
 
 #[cfg(feature = "blocking")]
+use embedded_hal::delay::DelayNs;
+#[cfg(feature = "async")]
+use embedded_hal_async::delay::DelayNs;
+
+/// Interval between two polls of the status register while waiting for the sensor.
+#[cfg(any(feature = "blocking", feature = "async"))]
+const POLL_INTERVAL_MS: u32 = 10;
+
+/// Time [`Ens160::take_n_samples()`] waits for each measurement.
+#[cfg(any(feature = "blocking", feature = "async"))]
+const SAMPLE_TIMEOUT_MS: u32 = 2000;
+
+#[cfg(feature = "blocking")]
+impl<I2C, E> Ens160<I2C>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Creates a new sensor driver, resets the sensor and waits [`ENS160_RESET_DELAY_MS`]
+    /// for it to settle.
+    ///
+    /// Use this constructor if the sensor may be in any state, e.g. after the MCU crashed.
+    pub fn from_reset<D: DelayNs>(i2c: I2C, address: u8, delay: &mut D) -> Result<Self, Error<E>> {
+        let ens160 = Self::new(i2c, address).reset()?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS);
+        Ok(ens160)
+    }
+}
+
+#[cfg(feature = "async")]
 impl<I2C, E> Ens160<I2C>
 where
-    I2C: I2c<Error = E>,
+    I2C: Interface<Error = E>,
+{
+    /// Creates a new sensor driver, resets the sensor and waits [`ENS160_RESET_DELAY_MS`]
+    /// for it to settle.
+    ///
+    /// Use this constructor if the sensor may be in any state, e.g. after the MCU crashed.
+    pub async fn from_reset<D: DelayNs>(
+        i2c: I2C,
+        address: u8,
+        delay: &mut D,
+    ) -> Result<Self, Error<E>> {
+        let ens160 = Self::new(i2c, address).reset().await?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS).await;
+        Ok(ens160)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<I2C, M, E> Ens160<I2C, M>
+where
+    I2C: Interface<Error = E>,
 {
     /// Resets the device.
-    pub fn reset(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Reset as u8])
+    ///
+    /// The operation mode after the reset is unknown.
+    pub fn reset(mut self) -> Result<Ens160<I2C, mode::Unknown>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Reset as u8])?;
+        Ok(self.into_mode())
     }
 
     /// Switches the device to idle mode.
     ///
-    /// Only in idle mode operations with `ENS160_COMMAND_REG` can be performed.
-    pub fn idle(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Idle as u8])
+    /// Only in idle mode operations with `ENS160_COMMAND_REG` can be performed, see
+    /// [`Ens160::firmware_version()`] and [`Ens160::clear_command()`].
+    pub fn idle(mut self) -> Result<Ens160<I2C, mode::Idle>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Idle as u8])?;
+        Ok(self.into_mode())
     }
 
     /// Switches the device to deep sleep mode.
     ///
     /// This function can be used to conserve power when the device is not in use.
-    pub fn deep_sleep(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Sleep as u8])
+    pub fn deep_sleep(mut self) -> Result<Ens160<I2C, mode::Sleep>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Sleep as u8])?;
+        Ok(self.into_mode())
+    }
+
+    /// Switches the device to deep sleep mode and releases the underlying I2C bus.
+    ///
+    /// The bus is returned even if switching to deep sleep failed.
+    pub fn deep_sleep_and_release(mut self) -> (Result<(), Error<E>>, I2C) {
+        let result = self.write_register([ENS160_OPMODE_REG, OperationMode::Sleep as u8]);
+        (result, self.i2c)
     }
 
     /// Switches the device to operational mode.
     ///
     /// Call this function when you want the device to start taking measurements.
-    pub fn operational(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Standard as u8])
+    pub fn operational(mut self) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Standard as u8])?;
+        Ok(self.into_mode())
     }
 
-    /// Clears the command register of the device.
-    pub fn clear_command(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_COMMAND_REG, Command::Nop as u8])?;
-        self.write_register([ENS160_COMMAND_REG, Command::Clear as u8])?;
-        Ok(())
+    /// Resets the device, waits [`ENS160_RESET_DELAY_MS`] for it to settle, switches to idle
+    /// mode and clears the command register.
+    ///
+    /// The returned device is ready for configuration.
+    pub fn reset_and_initialize<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Ens160<I2C, mode::Idle>, Error<E>> {
+        let ens160 = self.reset()?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS);
+        let mut ens160 = ens160.idle()?;
+        ens160.clear_command()?;
+        Ok(ens160)
+    }
+
+    /// Performs the standard startup sequence and returns the device in operational mode.
+    ///
+    /// Resets the device, waits [`ENS160_RESET_DELAY_MS`], verifies the part ID, switches to
+    /// idle mode, clears the command register, writes the compensation values which are `Some`
+    /// and switches to operational mode. See [`Ens160Builder`](crate::Ens160Builder) for
+    /// further configuration.
+    pub fn initialize<D: DelayNs>(
+        self,
+        delay: &mut D,
+        temp_scaled: Option<i16>,
+        hum_scaled: Option<u16>,
+    ) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        let mut ens160 = self.reset()?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS);
+        ens160.verify_part_id()?;
+        let mut ens160 = ens160.idle()?;
+        ens160.clear_command()?;
+        match (temp_scaled, hum_scaled) {
+            (Some(temp), Some(hum)) => ens160.set_temp_and_hum(temp, hum)?,
+            (Some(temp), None) => ens160.set_temp(temp)?,
+            (None, Some(hum)) => ens160.set_hum(hum)?,
+            (None, None) => {}
+        }
+        ens160.operational()
+    }
+
+    /// Reads back the operation mode from the `OPMODE` register.
+    ///
+    /// Returns [`Error::InvalidMode`] if the register holds an unknown value.
+    pub fn read_operation_mode(&mut self) -> Result<OperationMode, Error<E>> {
+        let mode = self.read_register::<1>(ENS160_OPMODE_REG)?[0];
+        OperationMode::try_from(mode).map_err(|e| Error::InvalidMode(e.value()))
+    }
+
+    /// Checks that the sensor is responsive and in the `expected` operation mode.
+    ///
+    /// Returns [`Error::UnexpectedMode`] if the sensor is in another mode.
+    pub fn read_operation_mode_validated(
+        &mut self,
+        expected: OperationMode,
+    ) -> Result<(), Error<E>> {
+        let actual = self.read_operation_mode()?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedMode { actual, expected })
+        }
     }
 
     /// Returns the part ID of the sensor.
-    pub fn part_id(&mut self) -> Result<u16, E> {
+    pub fn part_id(&mut self) -> Result<u16, Error<E>> {
         self.read_register::<2>(ENS160_PART_ID_REG)
             .map(u16::from_le_bytes)
     }
 
-    /// Returns the firmware version of the sensor.
-    pub fn firmware_version(&mut self) -> Result<(u8, u8, u8), E> {
-        self.write_register([ENS160_COMMAND_REG, Command::GetAppVersion as u8])?;
-        let buffer = self.read_register::<3>(ENS160_GPR_READ_REG)?;
-        Ok((buffer[0], buffer[1], buffer[2]))
+    /// Checks that the device is an `ENS160` by comparing its part ID with [`ENS160_PART_ID`].
+    ///
+    /// Returns [`Error::InvalidPartId`] if another device answered at the address.
+    pub fn verify_part_id(&mut self) -> Result<(), Error<E>> {
+        let part_id = self.part_id()?;
+        if part_id == ENS160_PART_ID {
+            Ok(())
+        } else {
+            Err(Error::InvalidPartId(part_id))
+        }
+    }
+
+    /// Checks whether an `ENS160` answers at the configured address.
+    ///
+    /// Returns `Ok(false)` if a device answered with another part ID. A missing device usually
+    /// shows up as a NACK and is returned as [`Error::I2c`], because the [`Interface`] doesn't
+    /// tell NACKs apart from other bus errors. For I2C buses match the error's
+    /// `embedded_hal::i2c::Error::kind()` against `ErrorKind::NoAcknowledge` to detect it.
+    pub fn probe(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.part_id()? == ENS160_PART_ID)
+    }
+
+    /// Checks that the sensor accepts writes and answers with its part ID.
+    ///
+    /// Writes a NOP to the command register and verifies the part ID, see
+    /// [`Ens160::verify_part_id()`]. Neither changes the state of the sensor, so this can be
+    /// called in any operation mode.
+    pub fn verify_communication(&mut self) -> Result<(), Error<E>> {
+        self.write_register([ENS160_COMMAND_REG, Command::Nop as u8])?;
+        self.verify_part_id()
     }
 
     /// Returns the current status of the sensor.
-    pub fn status(&mut self) -> Result<Status, E> {
+    pub fn status(&mut self) -> Result<Status, Error<E>> {
         self.read_register::<1>(ENS160_DATA_STATUS_REG)
             .map(|v| Status(v[0]))
     }
 
+    /// Polls the status register until new data is ready.
+    ///
+    /// The status is polled every 10 ms. Returns [`Error::WaitTimeout`] if no data got ready
+    /// within `timeout_ms`.
+    pub fn wait_for_data_ready<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<Status, Error<E>> {
+        let mut waited_ms: u32 = 0;
+        loop {
+            let status = self.status()?;
+            if status.data_is_ready() {
+                return Ok(status);
+            }
+            if waited_ms >= timeout_ms {
+                return Err(Error::WaitTimeout);
+            }
+            delay.delay_ms(POLL_INTERVAL_MS);
+            waited_ms = waited_ms.saturating_add(POLL_INTERVAL_MS);
+        }
+    }
+
+    /// Returns `true` if new data is ready and the sensor operates normally.
+    pub fn is_ready(&mut self) -> Result<bool, Error<E>> {
+        let status = self.status()?;
+        Ok(status.data_is_ready() && status.validity_flag().is_stable())
+    }
+
+    /// Polls [`is_ready()`](Self::is_ready) every `poll_interval_ms` until it returns `true`.
+    ///
+    /// A poll interval of 0 is treated as 1 ms. Returns [`Error::WaitTimeout`] if the sensor
+    /// didn't get ready within `timeout_ms`.
+    pub fn wait_ready_with_delay<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_ms: u32,
+        timeout_ms: u32,
+    ) -> Result<(), Error<E>> {
+        let poll_interval_ms = poll_interval_ms.max(1);
+        let mut waited_ms: u32 = 0;
+        loop {
+            if self.is_ready()? {
+                return Ok(());
+            }
+            if waited_ms >= timeout_ms {
+                return Err(Error::WaitTimeout);
+            }
+            delay.delay_ms(poll_interval_ms);
+            waited_ms = waited_ms.saturating_add(poll_interval_ms);
+        }
+    }
+
     /// Returns the current Air Quality Index (AQI) reading from the sensor.
     ///
     /// The AQI is calculated based on the current sensor readings.
-    pub fn airquality_index(&mut self) -> Result<AirQualityIndex, E> {
+    pub fn airquality_index(&mut self) -> Result<AirQualityIndex, Error<E>> {
         self.read_register::<1>(ENS160_DATA_AQI_REG)
             .map(|v| AirQualityIndex::from(v[0] & 0x07))
     }
@@ -92,43 +450,190 @@ where
     /// Returns the Total Volatile Organic Compounds (TVOC) measurement from the sensor.
     ///
     /// The TVOC level is expressed in parts per billion (ppb) in the range 0-65000.
-    pub fn tvoc(&mut self) -> Result<u16, E> {
+    pub fn tvoc(&mut self) -> Result<Tvoc, Error<E>> {
         self.read_register::<2>(ENS160_DATA_TVOC_REG)
             .map(u16::from_le_bytes)
+            .map(Tvoc::from)
+    }
+
+    /// Returns the ethanol (EtOH) concentration measured by the sensor in ppb.
+    ///
+    /// The `DATA_ETOH` register shares its address with `DATA_TVOC`. While [`Ens160::tvoc()`]
+    /// interprets the value as the TVOC output of the algorithm, this method returns it as
+    /// ethanol-equivalent reading, which is useful for standalone alcohol detection.
+    pub fn read_etoh(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_ETOH_REG)
+            .map(u16::from_le_bytes)
     }
 
     /// Returns the Equivalent Carbon Dioxide (eCO2) measurement from the sensor.
     ///
     /// The eCO2 level is expressed in parts per million (ppm) in the range 400-65000.
-    pub fn eco2(&mut self) -> Result<ECo2, E> {
+    ///
+    /// During the warm-up and initial start-up phase the sensor can report values below 400 ppm,
+    /// including 0. Check [`Status::validity_flag()`] or [`ECo2::is_in_valid_range()`] before
+    /// relying on the value.
+    pub fn eco2(&mut self) -> Result<ECo2, Error<E>> {
         self.read_register::<2>(ENS160_DATA_ECO2_REG)
             .map(u16::from_le_bytes)
             .map(ECo2::from)
     }
 
+    /// Returns status, AQI, TVOC and eCO2 read in a single transaction.
+    ///
+    /// All values are guaranteed to originate from the same measurement cycle.
+    pub fn read_all(&mut self) -> Result<MeasurementBundle, Error<E>> {
+        self.read_output_burst()
+            .map(MeasurementBundle::from_registers)
+    }
+
+    /// Returns status and eCO2 read in a single transaction, see [`Ens160::read_all()`].
+    pub fn read_status_and_eco2(&mut self) -> Result<(Status, ECo2), Error<E>> {
+        self.read_all()
+            .map(|bundle| (bundle.status, bundle.eco2.value))
+    }
+
+    /// Returns the measurements if the sensor is fully operational and new data is ready,
+    /// otherwise `None`.
+    ///
+    /// The status is read in the same transaction as the measurements, see
+    /// [`Status::measurement_is_fresh()`].
+    pub fn sample(&mut self) -> Result<Option<MeasurementBundle>, Error<E>> {
+        let bundle = self.read_all()?;
+        Ok(bundle.status.measurement_is_fresh().then_some(bundle))
+    }
+
+    /// Collects `n` measurements into [`SensorStats`], waiting `delay_ms` between them.
+    ///
+    /// Each measurement waits up to 2 s for new data, see [`Ens160::wait_for_data_ready()`].
+    /// Measurements without a normal validity flag are counted in
+    /// [`SensorStats::skipped()`] instead of being added.
+    pub fn take_n_samples<D: DelayNs>(
+        &mut self,
+        n: u8,
+        delay_ms: u32,
+        delay: &mut D,
+    ) -> Result<SensorStats, Error<E>> {
+        let mut stats = SensorStats::new();
+        for i in 0..n {
+            if i > 0 {
+                delay.delay_ms(delay_ms);
+            }
+            self.wait_for_data_ready(delay, SAMPLE_TIMEOUT_MS)?;
+            let bundle = self.read_all()?;
+            if bundle.is_valid() {
+                stats.update(&bundle);
+            } else {
+                stats.skip();
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Returns `true` if the eCO2 or TVOC concentration is above the thresholds set with
+    /// [`Ens160::set_ventilation_thresholds()`].
+    ///
+    /// Measurements taken while the sensor isn't operating normally, e.g. during warm-up,
+    /// never require ventilation.
+    pub fn ventilation_needed(&mut self) -> Result<bool, Error<E>> {
+        let bundle = self.read_all()?;
+        Ok(self.exceeds_ventilation_thresholds(&bundle))
+    }
+
+    /// Reads all measurements and rates the eCO2 and TVOC concentrations, returned in this
+    /// order, against the thresholds set with [`Ens160::with_alarm_config()`].
+    ///
+    /// Pass the levels returned by the previous call as `previous` to apply the hysteresis of
+    /// the config, see [`AlarmConfig::check_alarms_with_previous()`]. Without thresholds and for
+    /// measurements taken while the sensor isn't operating normally both levels are
+    /// [`AlarmLevel::Ok`].
+    pub fn alarms_triggered(
+        &mut self,
+        previous: (AlarmLevel, AlarmLevel),
+    ) -> Result<(AlarmLevel, AlarmLevel), Error<E>> {
+        let bundle = self.read_all()?;
+        Ok(self.check_alarms(&bundle, previous))
+    }
+
+    /// Returns the eCO2 reading, or [`nb::Error::WouldBlock`] if no new data is ready.
+    #[cfg(feature = "nb")]
+    pub fn try_read_eco2(&mut self) -> nb::Result<ECo2, Error<E>> {
+        self.check_data_ready()?;
+        Ok(self.eco2()?)
+    }
+
+    /// Returns the TVOC reading, or [`nb::Error::WouldBlock`] if no new data is ready.
+    #[cfg(feature = "nb")]
+    pub fn try_read_tvoc(&mut self) -> nb::Result<Tvoc, Error<E>> {
+        self.check_data_ready()?;
+        Ok(self.tvoc()?)
+    }
+
+    /// Returns the AQI reading, or [`nb::Error::WouldBlock`] if no new data is ready.
+    #[cfg(feature = "nb")]
+    pub fn try_read_aqi(&mut self) -> nb::Result<AirQualityIndex, Error<E>> {
+        self.check_data_ready()?;
+        Ok(self.airquality_index()?)
+    }
+
+    /// Returns all measurements, or [`nb::Error::WouldBlock`] if no new data is ready.
+    ///
+    /// The status is read together with the measurements, so this needs a single transaction
+    /// when data is ready.
+    #[cfg(feature = "nb")]
+    pub fn try_read_all(&mut self) -> nb::Result<MeasurementBundle, Error<E>> {
+        let bundle = self.read_all()?;
+        if bundle.status.data_is_ready() {
+            Ok(bundle)
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    #[cfg(feature = "nb")]
+    fn check_data_ready(&mut self) -> nb::Result<(), Error<E>> {
+        if self.status()?.data_is_ready() {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
     /// Returns the temperature (in °C) and relative humidity (in %) values used in the calculations.
     ///
     /// The units are scaled by 100. For example, a temperature value of 2550 represents 25.50 °C,
     /// and a humidity value of 5025 represents 50.25% RH.
     ///
     /// These values can be set using [`Ens160::set_temp_and_hum()`].
-    pub fn temp_and_hum(&mut self) -> Result<(i16, u16), E> {
+    pub fn temp_and_hum(&mut self) -> Result<(i16, u16), Error<E>> {
         let buffer = self.read_register::<4>(ENS160_DATA_T_REG)?;
         let temp = u16::from_le_bytes([buffer[0], buffer[1]]);
         let rh = u16::from_le_bytes([buffer[2], buffer[3]]);
 
-        let temp = temp as i32 * 100 / 64 - 27315;
-        let hum = rh as u32 * 100 / 512;
+        Ok((decode_temp(temp), decode_hum(rh)))
+    }
+
+    /// Returns the temperature used in the calculations, scaled by 100 like in
+    /// [`Ens160::temp_and_hum()`].
+    pub fn read_data_temp(&mut self) -> Result<i16, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_T_REG)
+            .map(|v| decode_temp(u16::from_le_bytes(v)))
+    }
 
-        Ok((temp as i16, hum as u16))
+    /// Returns the relative humidity used in the calculations, scaled by 100 like in
+    /// [`Ens160::temp_and_hum()`]. For example, 5000 represents 50.00% RH.
+    pub fn read_data_rh(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_RH_REG)
+            .map(|v| decode_hum(u16::from_le_bytes(v)))
     }
 
     /// Sets the temperature value used in the device's calculations.
     ///
     /// Unit is scaled by 100. For example, a temperature value of 2550 should be used for 25.50 °C.
-    pub fn set_temp(&mut self, ambient_temp: i16) -> Result<(), E> {
-        let temp = ((ambient_temp as i32 + 27315) * 64 / 100) as u16;
-        let temp = temp.to_le_bytes();
+    /// Values outside of -40.00 °C to 85.00 °C are rejected with [`Error::OutOfRange`].
+    pub fn set_temp(&mut self, ambient_temp: i16) -> Result<(), Error<E>> {
+        check_temp(ambient_temp)?;
+        let temp = encode_temp(ambient_temp).to_le_bytes();
         let tbuffer = [ENS160_TEMP_IN_REG, temp[0], temp[1]];
         self.write_register(tbuffer)
     }
@@ -136,180 +641,2281 @@ where
     /// Sets the relative humidity value used in the device's calculations.
     ///
     /// Unit is scaled by 100. For example, a humidity value of 5025 should be used for 50.25% RH.
-    pub fn set_hum(&mut self, relative_humidity: u16) -> Result<(), E> {
-        let rh = (relative_humidity as u32 * 512 / 100) as u16;
-        let rh = rh.to_le_bytes();
+    /// Values above 100.00% RH are rejected with [`Error::OutOfRange`].
+    pub fn set_hum(&mut self, relative_humidity: u16) -> Result<(), Error<E>> {
+        check_hum(relative_humidity)?;
+        let rh = encode_hum(relative_humidity).to_le_bytes();
         let hbuffer = [ENS160_RH_IN_REG, rh[0], rh[1]];
         self.write_register(hbuffer)
     }
 
-    /// Sets interrupt configuration.
-    pub fn set_interrupt_config(&mut self, config: InterruptConfig) -> Result<(), E> {
-        self.write_register([ENS160_CONFIG_REG, config.finish().0])
+    /// Sets the temperature and relative humidity values used in the device's calculations
+    /// in a single transaction.
+    ///
+    /// Units are scaled by 100, see [`Ens160::set_temp()`] and [`Ens160::set_hum()`].
+    pub fn set_temp_and_hum(
+        &mut self,
+        ambient_temp: i16,
+        relative_humidity: u16,
+    ) -> Result<(), Error<E>> {
+        check_temp(ambient_temp)?;
+        check_hum(relative_humidity)?;
+        let temp = encode_temp(ambient_temp).to_le_bytes();
+        let rh = encode_hum(relative_humidity).to_le_bytes();
+        let buffer = [ENS160_TEMP_IN_REG, temp[0], temp[1], rh[0], rh[1]];
+        self.write_register(buffer)
     }
 
-    fn read_register<const N: usize>(&mut self, register: u8) -> Result<[u8; N], E> {
-        let mut write_buffer = [0u8; 1];
-        write_buffer[0] = register;
-        let mut buffer = [0u8; N];
-        self.i2c
-            .write_read(self.address, &write_buffer, &mut buffer)?;
-        Ok(buffer)
+    /// Reads the temperature and relative humidity from a companion sensor and writes them
+    /// with [`Ens160::set_temp_and_hum()`].
+    pub fn set_compensation_from<S: TemperatureHumiditySensor>(
+        &mut self,
+        sensor: &mut S,
+    ) -> Result<(), Error<E>> {
+        let temp = sensor.read_temp_scaled_100();
+        let hum = sensor.read_hum_scaled_100();
+        self.set_temp_and_hum(temp, hum)
     }
 
-    fn write_register<const N: usize>(&mut self, buffer: [u8; N]) -> Result<(), E> {
-        self.i2c.write(self.address, &buffer)
+    /// Reads back the temperature written to `TEMP_IN`, scaled by 100 like in
+    /// [`Ens160::set_temp()`].
+    ///
+    /// Unlike [`Ens160::temp_and_hum()`] this returns the last written value, not the one used
+    /// in the calculations, which makes it useful to verify a compensation write.
+    pub fn read_temp_in(&mut self) -> Result<i16, Error<E>> {
+        self.read_register::<2>(ENS160_TEMP_IN_REG)
+            .map(|v| decode_temp(u16::from_le_bytes(v)))
     }
-}
 
-#[cfg(feature = "async")]
-impl<I2C, E> Ens160<I2C>
-where
-    I2C: I2c<SevenBitAddress, Error = E>,
-{
-    /// Resets the device.
-    pub async fn reset(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Reset as u8])
-            .await
+    /// Reads back the relative humidity written to `RH_IN`, scaled by 100 like in
+    /// [`Ens160::set_hum()`].
+    pub fn read_hum_in(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_RH_IN_REG)
+            .map(|v| decode_hum(u16::from_le_bytes(v)))
     }
 
-    /// Switches the device to idle mode.
-    ///
-    /// Only in idle mode operations with `ENS160_COMMAND_REG` can be performed.
-    pub async fn idle(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Idle as u8])
-            .await
+    /// Returns the temperature used in the calculations in °C.
+    #[cfg(any(feature = "libm", feature = "hardware-float"))]
+    pub fn temp_celsius_f32(&mut self) -> Result<f32, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_T_REG)
+            .map(|v| decode_temp_f32(u16::from_le_bytes(v)))
     }
 
-    /// Switches the device to deep sleep mode.
+    /// Returns the relative humidity used in the calculations in %.
+    #[cfg(any(feature = "libm", feature = "hardware-float"))]
+    pub fn humidity_percent_f32(&mut self) -> Result<f32, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_RH_REG)
+            .map(|v| decode_hum_f32(u16::from_le_bytes(v)))
+    }
+
+    /// Sets the temperature used in the calculations in °C.
     ///
-    /// This function can be used to conserve power when the device is not in use.
-    pub async fn deep_sleep(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Sleep as u8])
-            .await
+    /// The value is rounded to 0.01 °C, see [`Ens160::set_temp()`]. NaN is rejected with
+    /// [`Error::OutOfRange`].
+    #[cfg(feature = "libm")]
+    pub fn set_temp_f32(&mut self, celsius: f32) -> Result<(), Error<E>> {
+        let ambient_temp = scale_f32("temperature", celsius, TEMP_MIN.into(), TEMP_MAX.into())?;
+        self.set_temp(ambient_temp as i16)
     }
 
-    /// Switches the device to operational mode.
+    /// Sets the relative humidity used in the calculations in %.
     ///
-    /// Call this function when you want the device to start taking measurements.
-    pub async fn operational(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_OPMODE_REG, OperationMode::Standard as u8])
-            .await
+    /// The value is rounded to 0.01%, see [`Ens160::set_hum()`]. NaN is rejected with
+    /// [`Error::OutOfRange`].
+    #[cfg(feature = "libm")]
+    pub fn set_hum_f32(&mut self, percent: f32) -> Result<(), Error<E>> {
+        let relative_humidity = scale_f32("humidity", percent, 0, HUM_MAX.into())?;
+        self.set_hum(relative_humidity as u16)
     }
 
-    /// Clears the command register of the device.
-    pub async fn clear_command(&mut self) -> Result<(), E> {
-        self.write_register([ENS160_COMMAND_REG, Command::Nop as u8])
-            .await?;
-        self.write_register([ENS160_COMMAND_REG, Command::Clear as u8])
-            .await?;
-        Ok(())
+    /// Returns the temperature used in the calculations in °C, rounded to 1/16 °C.
+    #[cfg(feature = "fixed")]
+    pub fn temp_celsius_fixed(&mut self) -> Result<I12F4, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_T_REG)
+            .map(|v| decode_temp_fixed(u16::from_le_bytes(v)))
     }
 
-    /// Returns the part ID of the sensor.
-    pub async fn part_id(&mut self) -> Result<u16, E> {
-        self.read_register::<2>(ENS160_PART_ID_REG)
-            .await
-            .map(u16::from_le_bytes)
+    /// Returns the relative humidity used in the calculations in %.
+    ///
+    /// The register holds the humidity in 1/512 %, so the value is returned without conversion.
+    #[cfg(feature = "fixed")]
+    pub fn humidity_percent_fixed(&mut self) -> Result<U7F9, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_RH_REG)
+            .map(|v| U7F9::from_bits(u16::from_le_bytes(v)))
     }
 
-    /// Returns the firmware version of the sensor.
-    pub async fn firmware_version(&mut self) -> Result<(u8, u8, u8), E> {
-        self.write_register([ENS160_COMMAND_REG, Command::GetAppVersion as u8])
-            .await?;
-        let buffer = self.read_register::<3>(ENS160_GPR_READ_REG).await?;
-        Ok((buffer[0], buffer[1], buffer[2]))
+    /// Sets the temperature used in the calculations in °C.
+    ///
+    /// The value is rounded to 0.01 °C, see [`Ens160::set_temp()`].
+    #[cfg(feature = "fixed")]
+    pub fn set_temp_fixed(&mut self, celsius: I12F4) -> Result<(), Error<E>> {
+        let ambient_temp = scale_fixed(celsius)?;
+        self.set_temp(ambient_temp)
     }
 
-    /// Returns the current status of the sensor.
-    pub async fn status(&mut self) -> Result<Status, E> {
-        self.read_register::<1>(ENS160_DATA_STATUS_REG)
-            .await
-            .map(|v| Status(v[0]))
+    /// Writes all 8 bytes of the general purpose write registers in one transaction.
+    pub fn write_gpr(&mut self, data: &[u8; 8]) -> Result<(), Error<E>> {
+        let mut buffer = [0u8; 9];
+        buffer[0] = ENS160_GPR_WRITE_REG;
+        buffer[1..].copy_from_slice(data);
+        self.write_register(buffer)
     }
 
-    /// Returns the current Air Quality Index (AQI) reading from the sensor.
+    /// Writes a single byte of the general purpose write registers.
     ///
-    /// The AQI is calculated based on the current sensor readings.
-    pub async fn airquality_index(&mut self) -> Result<AirQualityIndex, E> {
-        self.read_register::<1>(ENS160_DATA_AQI_REG)
-            .await
-            .map(|v| AirQualityIndex::from(v[0] & 0x07))
+    /// `index` has to be in the range 0-7, otherwise [`Error::InvalidIndex`] is returned.
+    #[doc(alias = "write_gpr_at")]
+    pub fn write_gpr_byte(&mut self, index: u8, value: u8) -> Result<(), Error<E>> {
+        if index >= 8 {
+            return Err(Error::InvalidIndex(index));
+        }
+        self.write_register([ENS160_GPR_WRITE_REG + index, value])
     }
 
-    /// Returns the Total Volatile Organic Compounds (TVOC) measurement from the sensor.
+    /// Reads all 8 bytes of the general purpose read registers in one transaction.
+    pub fn read_gpr(&mut self) -> Result<[u8; 8], Error<E>> {
+        self.read_register::<8>(ENS160_GPR_READ_REG)
+    }
+
+    /// Reads a single byte of the general purpose read registers.
     ///
-    /// The TVOC level is expressed in parts per billion (ppb) in the range 0-65000.
-    pub async fn tvoc(&mut self) -> Result<u16, E> {
-        self.read_register::<2>(ENS160_DATA_TVOC_REG)
-            .await
-            .map(u16::from_le_bytes)
+    /// `index` has to be in the range 0-7, otherwise [`Error::InvalidIndex`] is returned.
+    #[doc(alias = "read_gpr_at")]
+    pub fn read_gpr_byte(&mut self, index: u8) -> Result<u8, Error<E>> {
+        if index >= 8 {
+            return Err(Error::InvalidIndex(index));
+        }
+        self.read_register::<1>(ENS160_GPR_READ_REG + index)
+            .map(|v| v[0])
     }
 
-    /// Returns the Equivalent Carbon Dioxide (eCO2) measurement from the sensor.
+    /// Reads the general purpose read registers as four little endian raw resistance values,
+    /// one per hot plate.
     ///
-    /// The eCO2 level is expressed in parts per million (ppm) in the range 400-65000.
-    pub async fn eco2(&mut self) -> Result<ECo2, E> {
-        self.read_register::<2>(ENS160_DATA_ECO2_REG)
-            .await
-            .map(u16::from_le_bytes)
-            .map(ECo2::from)
+    /// The sensor only reports resistances there after it has been configured for a custom
+    /// measurement with [`Ens160::write_gpr()`]; otherwise the values are meaningless.
+    pub fn read_raw_resistances(&mut self) -> Result<[u16; 4], Error<E>> {
+        self.read_gpr()
+            .map(|v| core::array::from_fn(|i| u16::from_le_bytes([v[2 * i], v[2 * i + 1]])))
     }
 
-    /// Returns the temperature (in °C) and relative humidity (in %) values used in the calculations.
+    /// Returns the checksum of the previous read transaction of `DATA_` registers.
     ///
-    /// The units are scaled by 100. For example, a temperature value of 2550 represents 25.50 °C,
-    /// and a humidity value of 5025 represents 50.25% RH.
+    /// The sensor starts each transaction with a checksum of 0, there is no other seed value.
+    /// Every byte read is then folded in by shifting the checksum left by one bit, XORing the
+    /// polynomial x^8 + x^4 + x^3 + x^2 + 1 (`0x1D`, x^8 implicit) if the MSB was shifted out,
+    /// and XORing the byte.
+    pub fn read_misr(&mut self) -> Result<u8, Error<E>> {
+        self.read_register::<1>(ENS160_DATA_MISR_REG).map(|v| v[0])
+    }
+
+    /// Checks whether `data` matches the checksum the sensor calculated for the previous
+    /// read transaction of `DATA_` registers.
     ///
-    /// These values can be set using [`Ens160::set_temp_and_hum()`].
-    pub async fn temp_and_hum(&mut self) -> Result<(i16, u16), E> {
-        let buffer = self.read_register::<4>(ENS160_DATA_T_REG).await?;
-        let temp = u16::from_le_bytes([buffer[0], buffer[1]]);
-        let rh = u16::from_le_bytes([buffer[2], buffer[3]]);
+    /// Pass all bytes received in that transaction. Their checksum is calculated as described in
+    /// [`Ens160::read_misr()`]. A mismatch indicates the data got corrupted on the bus.
+    #[doc(alias = "verify_last_data_read")]
+    pub fn verify_last_read(&mut self, data: &[u8]) -> Result<bool, Error<E>> {
+        let misr = self.read_misr()?;
+        Ok(misr == compute_misr(data))
+    }
 
-        let temp = temp as i32 * 100 / 64 - 27315;
-        let hum = rh as u32 * 100 / 512;
+    /// Reads back the interrupt configuration from the `CONFIG` register.
+    pub fn read_interrupt_config(&mut self) -> Result<InterruptConfig, Error<E>> {
+        self.read_register::<1>(ENS160_CONFIG_REG)
+            .map(|v| InterruptConfig::from_raw(v[0]))
+    }
 
-        Ok((temp as i16, hum as u16))
+    /// Sets interrupt configuration.
+    pub fn set_interrupt_config(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+        self.write_register([ENS160_CONFIG_REG, config.finish().0])
     }
 
-    /// Sets the temperature value used in the device's calculations.
+    /// Reads `N` bytes starting at `register`.
     ///
-    /// Unit is scaled by 100. For example, a temperature value of 2550 should be used for 25.50 °C.
-    pub async fn set_temp(&mut self, ambient_temp: i16) -> Result<(), E> {
-        let temp = ((ambient_temp as i32 + 27315) * 64 / 100) as u16;
-        let temp = temp.to_le_bytes();
-        let tbuffer = [ENS160_TEMP_IN_REG, temp[0], temp[1]];
-        self.write_register(tbuffer).await
+    /// This bypasses all checks of the driver. Reading some registers has side effects,
+    /// e.g. on the checksum in `DATA_MISR`.
+    pub fn read_register_raw<const N: usize>(&mut self, register: u8) -> Result<[u8; N], Error<E>> {
+        self.read_register(register)
     }
 
-    /// Sets the relative humidity value used in the device's calculations.
+    /// Writes `buffer[1..]` starting at the register in `buffer[0]`.
     ///
-    /// Unit is scaled by 100. For example, a humidity value of 5025 should be used for 50.25% RH.
-    pub async fn set_hum(&mut self, relative_humidity: u16) -> Result<(), E> {
-        let rh = (relative_humidity as u32 * 512 / 100) as u16;
-        let rh = rh.to_le_bytes();
-        let hbuffer = [ENS160_RH_IN_REG, rh[0], rh[1]];
-        self.write_register(hbuffer).await
+    /// This bypasses all checks of the driver and can put the sensor in a state which doesn't
+    /// match the operation mode tracked by the driver.
+    pub fn write_register_raw<const N: usize>(&mut self, buffer: [u8; N]) -> Result<(), Error<E>> {
+        self.write_register(buffer)
     }
 
-    /// Sets interrupt configuration.
-    pub async fn set_interrupt_config(&mut self, config: InterruptConfig) -> Result<(), E> {
-        self.write_register([ENS160_CONFIG_REG, config.finish().0])
-            .await
+    /// Reads the contiguous output registers `DATA_STATUS` through `DATA_ECO2` in one transaction.
+    fn read_output_burst(&mut self) -> Result<[u8; 6], Error<E>> {
+        self.read_register(ENS160_DATA_STATUS_REG)
     }
 
-    async fn read_register<const N: usize>(&mut self, register: u8) -> Result<[u8; N], E> {
-        let mut write_buffer = [0u8; 1];
-        write_buffer[0] = register;
+    fn read_register<const N: usize>(&mut self, register: u8) -> Result<[u8; N], Error<E>> {
+        #[cfg(feature = "log")]
+        log::trace!("read register 0x{:02X}", register);
         let mut buffer = [0u8; N];
-        self.i2c
-            .write_read(self.address, &write_buffer, &mut buffer)
-            .await?;
+        if let Err(e) = self.i2c.read_register(self.address, register, &mut buffer) {
+            #[cfg(feature = "log")]
+            log::error!("read register 0x{:02X} failed", register);
+            return Err(Error::I2c(e));
+        }
+        #[cfg(feature = "log")]
+        log::trace!("read register 0x{:02X} -> {:02X?}", register, &buffer);
+        Ok(buffer)
+    }
+
+    fn write_register<const N: usize>(&mut self, buffer: [u8; N]) -> Result<(), Error<E>> {
+        #[cfg(feature = "log")]
+        log::trace!(
+            "write register 0x{:02X} data={:02X?}",
+            buffer[0],
+            &buffer[1..]
+        );
+        self.i2c.write_register(self.address, &buffer).map_err(|e| {
+            #[cfg(feature = "log")]
+            log::error!("write register 0x{:02X} failed", buffer[0]);
+            Error::I2c(e)
+        })
+    }
+}
+
+#[cfg(all(feature = "i2c-transaction", feature = "blocking"))]
+impl<I2C, M> Ens160<I2C, M>
+where
+    I2C: embedded_hal::i2c::I2c,
+{
+    /// Reads `N` bytes starting at `register` in a single I2C transaction.
+    ///
+    /// Uses [`I2c::transaction()`](embedded_hal::i2c::I2c::transaction) with a write of the register
+    /// address followed by a read. Shared bus devices keep the bus locked for the whole
+    /// transaction, even when their `write_read()` isn't atomic.
+    pub fn read_register_combined<const N: usize>(
+        &mut self,
+        register: u8,
+    ) -> Result<[u8; N], Error<I2C::Error>> {
+        let mut buffer = [0u8; N];
+        self.i2c.transaction(
+            self.address,
+            &mut [
+                embedded_hal::i2c::Operation::Write(&[register]),
+                embedded_hal::i2c::Operation::Read(&mut buffer),
+            ],
+        )?;
         Ok(buffer)
     }
+}
+
+#[cfg(feature = "blocking")]
+impl<I2C, E> Ens160<I2C, mode::Idle>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Clears the command register of the device.
+    pub fn clear_command(&mut self) -> Result<(), Error<E>> {
+        self.write_register([ENS160_COMMAND_REG, Command::Nop as u8])?;
+        self.write_register([ENS160_COMMAND_REG, Command::Clear as u8])?;
+        Ok(())
+    }
+
+    /// Performs a soft reset by sending `NOP` followed by `CLEAR` to the command register.
+    ///
+    /// Unlike [`Ens160::reset()`], which switches the operation mode to reset and reinitializes
+    /// the whole sensor, this only clears the general purpose read registers and the command
+    /// state. The sensor stays in idle mode and no settle delay is needed afterwards.
+    pub fn soft_reset(&mut self) -> Result<(), Error<E>> {
+        self.clear_command()
+    }
+
+    /// Writes a custom resistance baseline to the general purpose write registers `GPR_WRITE`,
+    /// e.g. to restore a baseline recorded in clean air during factory calibration.
+    ///
+    /// The sensor only accepts the baseline in idle mode. Instead of reading `OPMODE` and
+    /// failing at runtime, this is only available on a driver in [`mode::Idle`], so calling it
+    /// in another mode doesn't compile.
+    pub fn set_baseline(&mut self, baseline: &[u8; 8]) -> Result<(), Error<E>> {
+        self.write_gpr(baseline)
+    }
+
+    /// Returns the content of the general purpose read registers `GPR_READ`.
+    ///
+    /// These don't echo the `GPR_WRITE` registers written by [`Ens160::set_baseline()`], they
+    /// hold the result of the last command or measurement, e.g. the raw resistances, see
+    /// [`Ens160::read_raw_resistances()`]. Like [`Ens160::set_baseline()`] this is only
+    /// available in idle mode.
+    pub fn get_baseline(&mut self) -> Result<[u8; 8], Error<E>> {
+        self.read_gpr()
+    }
+
+    /// Returns the firmware version of the sensor.
+    pub fn firmware_version(&mut self) -> Result<FirmwareVersion, Error<E>> {
+        self.write_register([ENS160_COMMAND_REG, Command::GetAppVersion as u8])?;
+        let buffer = self.read_gpr()?;
+        Ok(FirmwareVersion::from((buffer[0], buffer[1], buffer[2])))
+    }
+
+    /// Returns `true` if the firmware of the sensor is `min` or newer.
+    pub fn firmware_version_is_supported(
+        &mut self,
+        min: FirmwareVersion,
+    ) -> Result<bool, Error<E>> {
+        Ok(self.firmware_version()? >= min)
+    }
+
+    /// Checks that the firmware of the sensor is `required` or newer.
+    ///
+    /// Returns [`Error::FirmwareTooOld`] if the firmware is older.
+    pub fn assert_firmware_version(&mut self, required: FirmwareVersion) -> Result<(), Error<E>> {
+        let actual = self.firmware_version()?;
+        if actual >= required {
+            Ok(())
+        } else {
+            Err(Error::FirmwareTooOld { actual, required })
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, M, E> Ens160<I2C, M>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Resets the device.
+    ///
+    /// The operation mode after the reset is unknown.
+    pub async fn reset(mut self) -> Result<Ens160<I2C, mode::Unknown>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Reset as u8])
+            .await?;
+        Ok(self.into_mode())
+    }
+
+    /// Switches the device to idle mode.
+    ///
+    /// Only in idle mode operations with `ENS160_COMMAND_REG` can be performed, see
+    /// [`Ens160::firmware_version()`] and [`Ens160::clear_command()`].
+    pub async fn idle(mut self) -> Result<Ens160<I2C, mode::Idle>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Idle as u8])
+            .await?;
+        Ok(self.into_mode())
+    }
+
+    /// Switches the device to deep sleep mode.
+    ///
+    /// This function can be used to conserve power when the device is not in use.
+    pub async fn deep_sleep(mut self) -> Result<Ens160<I2C, mode::Sleep>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Sleep as u8])
+            .await?;
+        Ok(self.into_mode())
+    }
+
+    /// Switches the device to deep sleep mode and releases the underlying I2C bus.
+    ///
+    /// The bus is returned even if switching to deep sleep failed.
+    pub async fn deep_sleep_and_release(mut self) -> (Result<(), Error<E>>, I2C) {
+        let result = self
+            .write_register([ENS160_OPMODE_REG, OperationMode::Sleep as u8])
+            .await;
+        (result, self.i2c)
+    }
+
+    /// Switches the device to operational mode.
+    ///
+    /// Call this function when you want the device to start taking measurements.
+    pub async fn operational(mut self) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        self.write_register([ENS160_OPMODE_REG, OperationMode::Standard as u8])
+            .await?;
+        Ok(self.into_mode())
+    }
+
+    /// Resets the device, waits [`ENS160_RESET_DELAY_MS`] for it to settle, switches to idle
+    /// mode and clears the command register.
+    ///
+    /// The returned device is ready for configuration.
+    pub async fn reset_and_initialize<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Ens160<I2C, mode::Idle>, Error<E>> {
+        let ens160 = self.reset().await?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS).await;
+        let mut ens160 = ens160.idle().await?;
+        ens160.clear_command().await?;
+        Ok(ens160)
+    }
+
+    /// Performs the standard startup sequence and returns the device in operational mode.
+    ///
+    /// Resets the device, waits [`ENS160_RESET_DELAY_MS`], verifies the part ID, switches to
+    /// idle mode, clears the command register, writes the compensation values which are `Some`
+    /// and switches to operational mode. See [`Ens160Builder`](crate::Ens160Builder) for
+    /// further configuration.
+    pub async fn initialize<D: DelayNs>(
+        self,
+        delay: &mut D,
+        temp_scaled: Option<i16>,
+        hum_scaled: Option<u16>,
+    ) -> Result<Ens160<I2C, mode::Standard>, Error<E>> {
+        let mut ens160 = self.reset().await?;
+        delay.delay_ms(ENS160_RESET_DELAY_MS).await;
+        ens160.verify_part_id().await?;
+        let mut ens160 = ens160.idle().await?;
+        ens160.clear_command().await?;
+        match (temp_scaled, hum_scaled) {
+            (Some(temp), Some(hum)) => ens160.set_temp_and_hum(temp, hum).await?,
+            (Some(temp), None) => ens160.set_temp(temp).await?,
+            (None, Some(hum)) => ens160.set_hum(hum).await?,
+            (None, None) => {}
+        }
+        ens160.operational().await
+    }
+
+    /// Reads back the operation mode from the `OPMODE` register.
+    ///
+    /// Returns [`Error::InvalidMode`] if the register holds an unknown value.
+    pub async fn read_operation_mode(&mut self) -> Result<OperationMode, Error<E>> {
+        let mode = self.read_register::<1>(ENS160_OPMODE_REG).await?[0];
+        OperationMode::try_from(mode).map_err(|e| Error::InvalidMode(e.value()))
+    }
+
+    /// Checks that the sensor is responsive and in the `expected` operation mode.
+    ///
+    /// Returns [`Error::UnexpectedMode`] if the sensor is in another mode.
+    pub async fn read_operation_mode_validated(
+        &mut self,
+        expected: OperationMode,
+    ) -> Result<(), Error<E>> {
+        let actual = self.read_operation_mode().await?;
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedMode { actual, expected })
+        }
+    }
+
+    /// Returns the part ID of the sensor.
+    pub async fn part_id(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_PART_ID_REG)
+            .await
+            .map(u16::from_le_bytes)
+    }
+
+    /// Checks that the device is an `ENS160` by comparing its part ID with [`ENS160_PART_ID`].
+    ///
+    /// Returns [`Error::InvalidPartId`] if another device answered at the address.
+    pub async fn verify_part_id(&mut self) -> Result<(), Error<E>> {
+        let part_id = self.part_id().await?;
+        if part_id == ENS160_PART_ID {
+            Ok(())
+        } else {
+            Err(Error::InvalidPartId(part_id))
+        }
+    }
+
+    /// Checks whether an `ENS160` answers at the configured address.
+    ///
+    /// Returns `Ok(false)` if a device answered with another part ID. A missing device usually
+    /// shows up as a NACK and is returned as [`Error::I2c`], because the [`Interface`] doesn't
+    /// tell NACKs apart from other bus errors. For I2C buses match the error's
+    /// `embedded_hal::i2c::Error::kind()` against `ErrorKind::NoAcknowledge` to detect it.
+    pub async fn probe(&mut self) -> Result<bool, Error<E>> {
+        Ok(self.part_id().await? == ENS160_PART_ID)
+    }
+
+    /// Checks that the sensor accepts writes and answers with its part ID.
+    ///
+    /// Writes a NOP to the command register and verifies the part ID, see
+    /// [`Ens160::verify_part_id()`]. Neither changes the state of the sensor, so this can be
+    /// called in any operation mode.
+    pub async fn verify_communication(&mut self) -> Result<(), Error<E>> {
+        self.write_register([ENS160_COMMAND_REG, Command::Nop as u8])
+            .await?;
+        self.verify_part_id().await
+    }
+
+    /// Returns the current status of the sensor.
+    pub async fn status(&mut self) -> Result<Status, Error<E>> {
+        self.read_register::<1>(ENS160_DATA_STATUS_REG)
+            .await
+            .map(|v| Status(v[0]))
+    }
+
+    /// Polls the status register until new data is ready.
+    ///
+    /// The status is polled every 10 ms using [`embassy_time::Timer`]. Returns
+    /// [`Error::WaitTimeout`] if no data got ready within `timeout_ms`.
+    #[cfg(feature = "embassy-time")]
+    pub async fn wait_for_data_ready_async(&mut self, timeout_ms: u32) -> Result<Status, Error<E>> {
+        let mut waited_ms: u32 = 0;
+        loop {
+            let status = self.status().await?;
+            if status.data_is_ready() {
+                return Ok(status);
+            }
+            if waited_ms >= timeout_ms {
+                return Err(Error::WaitTimeout);
+            }
+            embassy_time::Timer::after_millis(POLL_INTERVAL_MS.into()).await;
+            waited_ms = waited_ms.saturating_add(POLL_INTERVAL_MS);
+        }
+    }
+
+    /// Returns `true` if new data is ready and the sensor operates normally.
+    pub async fn is_ready(&mut self) -> Result<bool, Error<E>> {
+        let status = self.status().await?;
+        Ok(status.data_is_ready() && status.validity_flag().is_stable())
+    }
+
+    /// Polls the status register until new data is ready.
+    ///
+    /// The status is polled every 10 ms. Returns [`Error::WaitTimeout`] if no data got ready
+    /// within `timeout_ms`.
+    pub async fn wait_for_data_ready<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Result<Status, Error<E>> {
+        let mut waited_ms: u32 = 0;
+        loop {
+            let status = self.status().await?;
+            if status.data_is_ready() {
+                return Ok(status);
+            }
+            if waited_ms >= timeout_ms {
+                return Err(Error::WaitTimeout);
+            }
+            delay.delay_ms(POLL_INTERVAL_MS).await;
+            waited_ms = waited_ms.saturating_add(POLL_INTERVAL_MS);
+        }
+    }
+
+    /// Polls [`is_ready()`](Self::is_ready) every `poll_interval_ms` until it returns `true`.
+    ///
+    /// A poll interval of 0 is treated as 1 ms. Returns [`Error::WaitTimeout`] if the sensor
+    /// didn't get ready within `timeout_ms`.
+    pub async fn wait_ready_async<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        poll_interval_ms: u32,
+        timeout_ms: u32,
+    ) -> Result<(), Error<E>> {
+        let poll_interval_ms = poll_interval_ms.max(1);
+        let mut waited_ms: u32 = 0;
+        loop {
+            if self.is_ready().await? {
+                return Ok(());
+            }
+            if waited_ms >= timeout_ms {
+                return Err(Error::WaitTimeout);
+            }
+            delay.delay_ms(poll_interval_ms).await;
+            waited_ms = waited_ms.saturating_add(poll_interval_ms);
+        }
+    }
+
+    /// Returns the current Air Quality Index (AQI) reading from the sensor.
+    ///
+    /// The AQI is calculated based on the current sensor readings.
+    pub async fn airquality_index(&mut self) -> Result<AirQualityIndex, Error<E>> {
+        self.read_register::<1>(ENS160_DATA_AQI_REG)
+            .await
+            .map(|v| AirQualityIndex::from(v[0] & 0x07))
+    }
+
+    /// Returns the Total Volatile Organic Compounds (TVOC) measurement from the sensor.
+    ///
+    /// The TVOC level is expressed in parts per billion (ppb) in the range 0-65000.
+    pub async fn tvoc(&mut self) -> Result<Tvoc, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_TVOC_REG)
+            .await
+            .map(u16::from_le_bytes)
+            .map(Tvoc::from)
+    }
+
+    /// Returns the ethanol (EtOH) concentration measured by the sensor in ppb.
+    ///
+    /// The `DATA_ETOH` register shares its address with `DATA_TVOC`. While [`Ens160::tvoc()`]
+    /// interprets the value as the TVOC output of the algorithm, this method returns it as
+    /// ethanol-equivalent reading, which is useful for standalone alcohol detection.
+    pub async fn read_etoh(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_ETOH_REG)
+            .await
+            .map(u16::from_le_bytes)
+    }
+
+    /// Returns the Equivalent Carbon Dioxide (eCO2) measurement from the sensor.
+    ///
+    /// The eCO2 level is expressed in parts per million (ppm) in the range 400-65000.
+    ///
+    /// During the warm-up and initial start-up phase the sensor can report values below 400 ppm,
+    /// including 0. Check [`Status::validity_flag()`] or [`ECo2::is_in_valid_range()`] before
+    /// relying on the value.
+    pub async fn eco2(&mut self) -> Result<ECo2, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_ECO2_REG)
+            .await
+            .map(u16::from_le_bytes)
+            .map(ECo2::from)
+    }
+
+    /// Returns status, AQI, TVOC and eCO2 read in a single transaction.
+    ///
+    /// All values are guaranteed to originate from the same measurement cycle.
+    pub async fn read_all(&mut self) -> Result<MeasurementBundle, Error<E>> {
+        self.read_output_burst()
+            .await
+            .map(MeasurementBundle::from_registers)
+    }
+
+    /// Returns status and eCO2 read in a single transaction, see [`Ens160::read_all()`].
+    pub async fn read_status_and_eco2(&mut self) -> Result<(Status, ECo2), Error<E>> {
+        self.read_all()
+            .await
+            .map(|bundle| (bundle.status, bundle.eco2.value))
+    }
+
+    /// Returns the measurements if the sensor is fully operational and new data is ready,
+    /// otherwise `None`.
+    ///
+    /// The status is read in the same transaction as the measurements, see
+    /// [`Status::measurement_is_fresh()`].
+    pub async fn sample(&mut self) -> Result<Option<MeasurementBundle>, Error<E>> {
+        let bundle = self.read_all().await?;
+        Ok(bundle.status.measurement_is_fresh().then_some(bundle))
+    }
+
+    /// Collects `n` measurements into [`SensorStats`], waiting `delay_ms` between them.
+    ///
+    /// Each measurement waits up to 2 s for new data, see [`Ens160::wait_for_data_ready()`].
+    /// Measurements without a normal validity flag are counted in
+    /// [`SensorStats::skipped()`] instead of being added.
+    pub async fn take_n_samples<D: DelayNs>(
+        &mut self,
+        n: u8,
+        delay_ms: u32,
+        delay: &mut D,
+    ) -> Result<SensorStats, Error<E>> {
+        let mut stats = SensorStats::new();
+        for i in 0..n {
+            if i > 0 {
+                delay.delay_ms(delay_ms).await;
+            }
+            self.wait_for_data_ready(delay, SAMPLE_TIMEOUT_MS).await?;
+            let bundle = self.read_all().await?;
+            if bundle.is_valid() {
+                stats.update(&bundle);
+            } else {
+                stats.skip();
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Returns `true` if the eCO2 or TVOC concentration is above the thresholds set with
+    /// [`Ens160::set_ventilation_thresholds()`].
+    ///
+    /// Measurements taken while the sensor isn't operating normally, e.g. during warm-up,
+    /// never require ventilation.
+    pub async fn ventilation_needed(&mut self) -> Result<bool, Error<E>> {
+        let bundle = self.read_all().await?;
+        Ok(self.exceeds_ventilation_thresholds(&bundle))
+    }
+
+    /// Reads all measurements and rates the eCO2 and TVOC concentrations, returned in this
+    /// order, against the thresholds set with [`Ens160::with_alarm_config()`].
+    ///
+    /// Pass the levels returned by the previous call as `previous` to apply the hysteresis of
+    /// the config, see [`AlarmConfig::check_alarms_with_previous()`]. Without thresholds and for
+    /// measurements taken while the sensor isn't operating normally both levels are
+    /// [`AlarmLevel::Ok`].
+    pub async fn alarms_triggered(
+        &mut self,
+        previous: (AlarmLevel, AlarmLevel),
+    ) -> Result<(AlarmLevel, AlarmLevel), Error<E>> {
+        let bundle = self.read_all().await?;
+        Ok(self.check_alarms(&bundle, previous))
+    }
+
+    /// Returns the temperature (in °C) and relative humidity (in %) values used in the calculations.
+    ///
+    /// The units are scaled by 100. For example, a temperature value of 2550 represents 25.50 °C,
+    /// and a humidity value of 5025 represents 50.25% RH.
+    ///
+    /// These values can be set using [`Ens160::set_temp_and_hum()`].
+    pub async fn temp_and_hum(&mut self) -> Result<(i16, u16), Error<E>> {
+        let buffer = self.read_register::<4>(ENS160_DATA_T_REG).await?;
+        let temp = u16::from_le_bytes([buffer[0], buffer[1]]);
+        let rh = u16::from_le_bytes([buffer[2], buffer[3]]);
+
+        Ok((decode_temp(temp), decode_hum(rh)))
+    }
+
+    /// Returns the temperature used in the calculations, scaled by 100 like in
+    /// [`Ens160::temp_and_hum()`].
+    pub async fn read_data_temp(&mut self) -> Result<i16, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_T_REG)
+            .await
+            .map(|v| decode_temp(u16::from_le_bytes(v)))
+    }
+
+    /// Returns the relative humidity used in the calculations, scaled by 100 like in
+    /// [`Ens160::temp_and_hum()`]. For example, 5000 represents 50.00% RH.
+    pub async fn read_data_rh(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_RH_REG)
+            .await
+            .map(|v| decode_hum(u16::from_le_bytes(v)))
+    }
+
+    /// Sets the temperature value used in the device's calculations.
+    ///
+    /// Unit is scaled by 100. For example, a temperature value of 2550 should be used for 25.50 °C.
+    /// Values outside of -40.00 °C to 85.00 °C are rejected with [`Error::OutOfRange`].
+    pub async fn set_temp(&mut self, ambient_temp: i16) -> Result<(), Error<E>> {
+        check_temp(ambient_temp)?;
+        let temp = encode_temp(ambient_temp).to_le_bytes();
+        let tbuffer = [ENS160_TEMP_IN_REG, temp[0], temp[1]];
+        self.write_register(tbuffer).await
+    }
+
+    /// Sets the relative humidity value used in the device's calculations.
+    ///
+    /// Unit is scaled by 100. For example, a humidity value of 5025 should be used for 50.25% RH.
+    /// Values above 100.00% RH are rejected with [`Error::OutOfRange`].
+    pub async fn set_hum(&mut self, relative_humidity: u16) -> Result<(), Error<E>> {
+        check_hum(relative_humidity)?;
+        let rh = encode_hum(relative_humidity).to_le_bytes();
+        let hbuffer = [ENS160_RH_IN_REG, rh[0], rh[1]];
+        self.write_register(hbuffer).await
+    }
+
+    /// Sets the temperature and relative humidity values used in the device's calculations
+    /// in a single transaction.
+    ///
+    /// Units are scaled by 100, see [`Ens160::set_temp()`] and [`Ens160::set_hum()`].
+    pub async fn set_temp_and_hum(
+        &mut self,
+        ambient_temp: i16,
+        relative_humidity: u16,
+    ) -> Result<(), Error<E>> {
+        check_temp(ambient_temp)?;
+        check_hum(relative_humidity)?;
+        let temp = encode_temp(ambient_temp).to_le_bytes();
+        let rh = encode_hum(relative_humidity).to_le_bytes();
+        let buffer = [ENS160_TEMP_IN_REG, temp[0], temp[1], rh[0], rh[1]];
+        self.write_register(buffer).await
+    }
+
+    /// Reads the temperature and relative humidity from a companion sensor and writes them
+    /// with [`Ens160::set_temp_and_hum()`].
+    pub async fn set_compensation_from<S: TemperatureHumiditySensor>(
+        &mut self,
+        sensor: &mut S,
+    ) -> Result<(), Error<E>> {
+        let temp = sensor.read_temp_scaled_100();
+        let hum = sensor.read_hum_scaled_100();
+        self.set_temp_and_hum(temp, hum).await
+    }
+
+    /// Reads back the temperature written to `TEMP_IN`, scaled by 100 like in
+    /// [`Ens160::set_temp()`].
+    ///
+    /// Unlike [`Ens160::temp_and_hum()`] this returns the last written value, not the one used
+    /// in the calculations, which makes it useful to verify a compensation write.
+    pub async fn read_temp_in(&mut self) -> Result<i16, Error<E>> {
+        self.read_register::<2>(ENS160_TEMP_IN_REG)
+            .await
+            .map(|v| decode_temp(u16::from_le_bytes(v)))
+    }
+
+    /// Reads back the relative humidity written to `RH_IN`, scaled by 100 like in
+    /// [`Ens160::set_hum()`].
+    pub async fn read_hum_in(&mut self) -> Result<u16, Error<E>> {
+        self.read_register::<2>(ENS160_RH_IN_REG)
+            .await
+            .map(|v| decode_hum(u16::from_le_bytes(v)))
+    }
+
+    /// Returns the temperature used in the calculations in °C.
+    #[cfg(any(feature = "libm", feature = "hardware-float"))]
+    pub async fn temp_celsius_f32(&mut self) -> Result<f32, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_T_REG)
+            .await
+            .map(|v| decode_temp_f32(u16::from_le_bytes(v)))
+    }
+
+    /// Returns the relative humidity used in the calculations in %.
+    #[cfg(any(feature = "libm", feature = "hardware-float"))]
+    pub async fn humidity_percent_f32(&mut self) -> Result<f32, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_RH_REG)
+            .await
+            .map(|v| decode_hum_f32(u16::from_le_bytes(v)))
+    }
+
+    /// Sets the temperature used in the calculations in °C.
+    ///
+    /// The value is rounded to 0.01 °C, see [`Ens160::set_temp()`]. NaN is rejected with
+    /// [`Error::OutOfRange`].
+    #[cfg(feature = "libm")]
+    pub async fn set_temp_f32(&mut self, celsius: f32) -> Result<(), Error<E>> {
+        let ambient_temp = scale_f32("temperature", celsius, TEMP_MIN.into(), TEMP_MAX.into())?;
+        self.set_temp(ambient_temp as i16).await
+    }
+
+    /// Sets the relative humidity used in the calculations in %.
+    ///
+    /// The value is rounded to 0.01%, see [`Ens160::set_hum()`]. NaN is rejected with
+    /// [`Error::OutOfRange`].
+    #[cfg(feature = "libm")]
+    pub async fn set_hum_f32(&mut self, percent: f32) -> Result<(), Error<E>> {
+        let relative_humidity = scale_f32("humidity", percent, 0, HUM_MAX.into())?;
+        self.set_hum(relative_humidity as u16).await
+    }
+
+    /// Returns the temperature used in the calculations in °C, rounded to 1/16 °C.
+    #[cfg(feature = "fixed")]
+    pub async fn temp_celsius_fixed(&mut self) -> Result<I12F4, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_T_REG)
+            .await
+            .map(|v| decode_temp_fixed(u16::from_le_bytes(v)))
+    }
+
+    /// Returns the relative humidity used in the calculations in %.
+    ///
+    /// The register holds the humidity in 1/512 %, so the value is returned without conversion.
+    #[cfg(feature = "fixed")]
+    pub async fn humidity_percent_fixed(&mut self) -> Result<U7F9, Error<E>> {
+        self.read_register::<2>(ENS160_DATA_RH_REG)
+            .await
+            .map(|v| U7F9::from_bits(u16::from_le_bytes(v)))
+    }
+
+    /// Sets the temperature used in the calculations in °C.
+    ///
+    /// The value is rounded to 0.01 °C, see [`Ens160::set_temp()`].
+    #[cfg(feature = "fixed")]
+    pub async fn set_temp_fixed(&mut self, celsius: I12F4) -> Result<(), Error<E>> {
+        let ambient_temp = scale_fixed(celsius)?;
+        self.set_temp(ambient_temp).await
+    }
+
+    /// Writes all 8 bytes of the general purpose write registers in one transaction.
+    pub async fn write_gpr(&mut self, data: &[u8; 8]) -> Result<(), Error<E>> {
+        let mut buffer = [0u8; 9];
+        buffer[0] = ENS160_GPR_WRITE_REG;
+        buffer[1..].copy_from_slice(data);
+        self.write_register(buffer).await
+    }
+
+    /// Writes a single byte of the general purpose write registers.
+    ///
+    /// `index` has to be in the range 0-7, otherwise [`Error::InvalidIndex`] is returned.
+    #[doc(alias = "write_gpr_at")]
+    pub async fn write_gpr_byte(&mut self, index: u8, value: u8) -> Result<(), Error<E>> {
+        if index >= 8 {
+            return Err(Error::InvalidIndex(index));
+        }
+        self.write_register([ENS160_GPR_WRITE_REG + index, value])
+            .await
+    }
+
+    /// Reads all 8 bytes of the general purpose read registers in one transaction.
+    pub async fn read_gpr(&mut self) -> Result<[u8; 8], Error<E>> {
+        self.read_register::<8>(ENS160_GPR_READ_REG).await
+    }
+
+    /// Reads a single byte of the general purpose read registers.
+    ///
+    /// `index` has to be in the range 0-7, otherwise [`Error::InvalidIndex`] is returned.
+    #[doc(alias = "read_gpr_at")]
+    pub async fn read_gpr_byte(&mut self, index: u8) -> Result<u8, Error<E>> {
+        if index >= 8 {
+            return Err(Error::InvalidIndex(index));
+        }
+        self.read_register::<1>(ENS160_GPR_READ_REG + index)
+            .await
+            .map(|v| v[0])
+    }
+
+    /// Reads the general purpose read registers as four little endian raw resistance values,
+    /// one per hot plate.
+    ///
+    /// The sensor only reports resistances there after it has been configured for a custom
+    /// measurement with [`Ens160::write_gpr()`]; otherwise the values are meaningless.
+    pub async fn read_raw_resistances(&mut self) -> Result<[u16; 4], Error<E>> {
+        self.read_gpr()
+            .await
+            .map(|v| core::array::from_fn(|i| u16::from_le_bytes([v[2 * i], v[2 * i + 1]])))
+    }
+
+    /// Returns the checksum of the previous read transaction of `DATA_` registers.
+    ///
+    /// The sensor starts each transaction with a checksum of 0, there is no other seed value.
+    /// Every byte read is then folded in by shifting the checksum left by one bit, XORing the
+    /// polynomial x^8 + x^4 + x^3 + x^2 + 1 (`0x1D`, x^8 implicit) if the MSB was shifted out,
+    /// and XORing the byte.
+    pub async fn read_misr(&mut self) -> Result<u8, Error<E>> {
+        self.read_register::<1>(ENS160_DATA_MISR_REG)
+            .await
+            .map(|v| v[0])
+    }
+
+    /// Checks whether `data` matches the checksum the sensor calculated for the previous
+    /// read transaction of `DATA_` registers.
+    ///
+    /// Pass all bytes received in that transaction. Their checksum is calculated as described in
+    /// [`Ens160::read_misr()`]. A mismatch indicates the data got corrupted on the bus.
+    #[doc(alias = "verify_last_data_read")]
+    pub async fn verify_last_read(&mut self, data: &[u8]) -> Result<bool, Error<E>> {
+        let misr = self.read_misr().await?;
+        Ok(misr == compute_misr(data))
+    }
+
+    /// Reads back the interrupt configuration from the `CONFIG` register.
+    pub async fn read_interrupt_config(&mut self) -> Result<InterruptConfig, Error<E>> {
+        self.read_register::<1>(ENS160_CONFIG_REG)
+            .await
+            .map(|v| InterruptConfig::from_raw(v[0]))
+    }
+
+    /// Sets interrupt configuration.
+    pub async fn set_interrupt_config(&mut self, config: InterruptConfig) -> Result<(), Error<E>> {
+        self.write_register([ENS160_CONFIG_REG, config.finish().0])
+            .await
+    }
+
+    /// Reads `N` bytes starting at `register`.
+    ///
+    /// This bypasses all checks of the driver. Reading some registers has side effects,
+    /// e.g. on the checksum in `DATA_MISR`.
+    pub async fn read_register_raw<const N: usize>(
+        &mut self,
+        register: u8,
+    ) -> Result<[u8; N], Error<E>> {
+        self.read_register(register).await
+    }
+
+    /// Writes `buffer[1..]` starting at the register in `buffer[0]`.
+    ///
+    /// This bypasses all checks of the driver and can put the sensor in a state which doesn't
+    /// match the operation mode tracked by the driver.
+    pub async fn write_register_raw<const N: usize>(
+        &mut self,
+        buffer: [u8; N],
+    ) -> Result<(), Error<E>> {
+        self.write_register(buffer).await
+    }
+
+    /// Reads the contiguous output registers `DATA_STATUS` through `DATA_ECO2` in one transaction.
+    async fn read_output_burst(&mut self) -> Result<[u8; 6], Error<E>> {
+        self.read_register(ENS160_DATA_STATUS_REG).await
+    }
+
+    async fn read_register<const N: usize>(&mut self, register: u8) -> Result<[u8; N], Error<E>> {
+        #[cfg(feature = "log")]
+        log::trace!("read register 0x{:02X}", register);
+        let mut buffer = [0u8; N];
+        if let Err(e) = self
+            .i2c
+            .read_register(self.address, register, &mut buffer)
+            .await
+        {
+            #[cfg(feature = "log")]
+            log::error!("read register 0x{:02X} failed", register);
+            return Err(Error::I2c(e));
+        }
+        #[cfg(feature = "log")]
+        log::trace!("read register 0x{:02X} -> {:02X?}", register, &buffer);
+        Ok(buffer)
+    }
+
+    async fn write_register<const N: usize>(&mut self, buffer: [u8; N]) -> Result<(), Error<E>> {
+        #[cfg(feature = "log")]
+        log::trace!(
+            "write register 0x{:02X} data={:02X?}",
+            buffer[0],
+            &buffer[1..]
+        );
+        self.i2c
+            .write_register(self.address, &buffer)
+            .await
+            .map_err(|e| {
+                #[cfg(feature = "log")]
+                log::error!("write register 0x{:02X} failed", buffer[0]);
+                Error::I2c(e)
+            })
+    }
+}
+
+#[cfg(all(feature = "i2c-transaction", feature = "async"))]
+impl<I2C, M> Ens160<I2C, M>
+where
+    I2C: embedded_hal_async::i2c::I2c,
+{
+    /// Reads `N` bytes starting at `register` in a single I2C transaction.
+    ///
+    /// Uses [`I2c::transaction()`](embedded_hal_async::i2c::I2c::transaction) with a write of the register
+    /// address followed by a read. Shared bus devices keep the bus locked for the whole
+    /// transaction, even when their `write_read()` isn't atomic.
+    pub async fn read_register_combined<const N: usize>(
+        &mut self,
+        register: u8,
+    ) -> Result<[u8; N], Error<I2C::Error>> {
+        let mut buffer = [0u8; N];
+        self.i2c
+            .transaction(
+                self.address,
+                &mut [
+                    embedded_hal_async::i2c::Operation::Write(&[register]),
+                    embedded_hal_async::i2c::Operation::Read(&mut buffer),
+                ],
+            )
+            .await?;
+        Ok(buffer)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, E> Ens160<I2C, mode::Idle>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Clears the command register of the device.
+    pub async fn clear_command(&mut self) -> Result<(), Error<E>> {
+        self.write_register([ENS160_COMMAND_REG, Command::Nop as u8])
+            .await?;
+        self.write_register([ENS160_COMMAND_REG, Command::Clear as u8])
+            .await?;
+        Ok(())
+    }
+
+    /// Performs a soft reset by sending `NOP` followed by `CLEAR` to the command register.
+    ///
+    /// Unlike [`Ens160::reset()`], which switches the operation mode to reset and reinitializes
+    /// the whole sensor, this only clears the general purpose read registers and the command
+    /// state. The sensor stays in idle mode and no settle delay is needed afterwards.
+    pub async fn soft_reset(&mut self) -> Result<(), Error<E>> {
+        self.clear_command().await
+    }
+
+    /// Writes a custom resistance baseline to the general purpose write registers `GPR_WRITE`,
+    /// e.g. to restore a baseline recorded in clean air during factory calibration.
+    ///
+    /// The sensor only accepts the baseline in idle mode. Instead of reading `OPMODE` and
+    /// failing at runtime, this is only available on a driver in [`mode::Idle`], so calling it
+    /// in another mode doesn't compile.
+    pub async fn set_baseline(&mut self, baseline: &[u8; 8]) -> Result<(), Error<E>> {
+        self.write_gpr(baseline).await
+    }
+
+    /// Returns the content of the general purpose read registers `GPR_READ`.
+    ///
+    /// These don't echo the `GPR_WRITE` registers written by [`Ens160::set_baseline()`], they
+    /// hold the result of the last command or measurement, e.g. the raw resistances, see
+    /// [`Ens160::read_raw_resistances()`]. Like [`Ens160::set_baseline()`] this is only
+    /// available in idle mode.
+    pub async fn get_baseline(&mut self) -> Result<[u8; 8], Error<E>> {
+        self.read_gpr().await
+    }
+
+    /// Returns the firmware version of the sensor.
+    pub async fn firmware_version(&mut self) -> Result<FirmwareVersion, Error<E>> {
+        self.write_register([ENS160_COMMAND_REG, Command::GetAppVersion as u8])
+            .await?;
+        let buffer = self.read_gpr().await?;
+        Ok(FirmwareVersion::from((buffer[0], buffer[1], buffer[2])))
+    }
+
+    /// Returns `true` if the firmware of the sensor is `min` or newer.
+    pub async fn firmware_version_is_supported(
+        &mut self,
+        min: FirmwareVersion,
+    ) -> Result<bool, Error<E>> {
+        Ok(self.firmware_version().await? >= min)
+    }
+
+    /// Checks that the firmware of the sensor is `required` or newer.
+    ///
+    /// Returns [`Error::FirmwareTooOld`] if the firmware is older.
+    pub async fn assert_firmware_version(
+        &mut self,
+        required: FirmwareVersion,
+    ) -> Result<(), Error<E>> {
+        let actual = self.firmware_version().await?;
+        if actual >= required {
+            Ok(())
+        } else {
+            Err(Error::FirmwareTooOld { actual, required })
+        }
+    }
+}
+
+/// Lowest compensation temperature supported by the sensor in °C scaled by 100.
+const TEMP_MIN: i16 = -4000;
+/// Highest compensation temperature supported by the sensor in °C scaled by 100.
+const TEMP_MAX: i16 = 8500;
+/// Highest compensation humidity in % scaled by 100.
+const HUM_MAX: u16 = 10000;
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn check_temp<E>(ambient_temp: i16) -> Result<(), Error<E>> {
+    if (TEMP_MIN..=TEMP_MAX).contains(&ambient_temp) {
+        Ok(())
+    } else {
+        Err(Error::OutOfRange {
+            field: "temperature",
+            value: ambient_temp.into(),
+            min: TEMP_MIN.into(),
+            max: TEMP_MAX.into(),
+        })
+    }
+}
+
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn check_hum<E>(relative_humidity: u16) -> Result<(), Error<E>> {
+    if relative_humidity <= HUM_MAX {
+        Ok(())
+    } else {
+        Err(Error::OutOfRange {
+            field: "humidity",
+            value: relative_humidity.into(),
+            min: 0,
+            max: HUM_MAX.into(),
+        })
+    }
+}
+
+/// Converts a temperature in °C scaled by 100 into the `TEMP_IN` register format (Kelvin * 64).
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn encode_temp(ambient_temp: i16) -> u16 {
+    ((ambient_temp as i32 + 27315) * 64 / 100) as u16
+}
+
+/// Converts a relative humidity in % scaled by 100 into the `RH_IN` register format (% * 512).
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn encode_hum(relative_humidity: u16) -> u16 {
+    (relative_humidity as u32 * 512 / 100) as u16
+}
+
+/// Converts the `TEMP_IN` or `DATA_T` register format (Kelvin * 64) into °C scaled by 100.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn decode_temp(raw: u16) -> i16 {
+    (raw as i32 * 100 / 64 - 27315) as i16
+}
+
+/// Converts the `RH_IN` or `DATA_RH` register format (% * 512) into % scaled by 100.
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn decode_hum(raw: u16) -> u16 {
+    (raw as u32 * 100 / 512) as u16
+}
+
+/// Scales a float by 100 and rounds it, rejecting values outside of `min..=max` and NaN.
+#[cfg(all(feature = "libm", any(feature = "blocking", feature = "async")))]
+fn scale_f32<E>(field: &'static str, value: f32, min: i32, max: i32) -> Result<i32, Error<E>> {
+    // `as` saturates for infinite values; NaN is mapped below `min` to be rejected.
+    let scaled = if value.is_nan() {
+        i32::MIN
+    } else {
+        libm::roundf(value * 100.0) as i32
+    };
+    if (min..=max).contains(&scaled) {
+        Ok(scaled)
+    } else {
+        Err(Error::OutOfRange {
+            field,
+            value: scaled,
+            min,
+            max,
+        })
+    }
+}
+
+/// Converts the `DATA_T` register format (Kelvin * 64) into °C.
+#[cfg(all(
+    any(feature = "libm", feature = "hardware-float"),
+    any(feature = "blocking", feature = "async")
+))]
+fn decode_temp_f32(raw: u16) -> f32 {
+    f32::from(raw) / 64.0 - 273.15
+}
+
+/// Converts the `DATA_RH` register format (% * 512) into %.
+#[cfg(all(
+    any(feature = "libm", feature = "hardware-float"),
+    any(feature = "blocking", feature = "async")
+))]
+fn decode_hum_f32(raw: u16) -> f32 {
+    f32::from(raw) / 512.0
+}
+
+/// Converts the `DATA_T` register format (Kelvin * 64) into °C, rounded to 1/16 °C.
+#[cfg(all(feature = "fixed", any(feature = "blocking", feature = "async")))]
+fn decode_temp_fixed(raw: u16) -> I12F4 {
+    // °C * 16 = raw / 4 - 273.15 * 16, scaled by 20 to stay in integers.
+    let scaled = i32::from(raw) * 5 - 87408;
+    // The result lies within -4370..=12013, which fits into an `i16`.
+    I12F4::from_bits((scaled + 10).div_euclid(20) as i16)
+}
+
+/// Converts a temperature in °C into °C scaled by 100, rejecting values out of range.
+#[cfg(all(feature = "fixed", any(feature = "blocking", feature = "async")))]
+fn scale_fixed<E>(celsius: I12F4) -> Result<i16, Error<E>> {
+    // °C * 100 = bits * 100 / 16, rounded to the nearest value.
+    let scaled = (i32::from(celsius.to_bits()) * 25 + 2).div_euclid(4);
+    if (i32::from(TEMP_MIN)..=i32::from(TEMP_MAX)).contains(&scaled) {
+        Ok(scaled as i16)
+    } else {
+        Err(Error::OutOfRange {
+            field: "temperature",
+            value: scaled,
+            min: TEMP_MIN.into(),
+            max: TEMP_MAX.into(),
+        })
+    }
+}
+
+/// Calculates the checksum of `data` the same way the `DATA_MISR` register does, see
+/// [`Ens160::read_misr()`].
+#[cfg(any(feature = "blocking", feature = "async"))]
+fn compute_misr(data: &[u8]) -> u8 {
+    data.iter().fold(0, |misr, &byte| {
+        let shifted = misr << 1;
+        let shifted = if misr & 0x80 != 0 {
+            shifted ^ MISR_POLYNOMIAL
+        } else {
+            shifted
+        };
+        shifted ^ byte
+    })
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod test {
+
+    use embedded_hal::i2c::{ErrorKind, I2c, NoAcknowledgeSource};
+    use embedded_hal_mock::eh1::delay::{CheckedDelay, Transaction as DelayTransaction};
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use super::{compute_misr, encode_hum, encode_temp, Ens160};
+    use crate::error::Error;
+    use crate::{
+        AirQualityIndex, AlarmConfig, AlarmLevel, ECo2, FirmwareVersion, InterruptConfig,
+        OperationMode, TemperatureHumiditySensor, Tvoc,
+    };
+
+    #[test]
+    fn test_compensation_encoding() {
+        assert_eq!(encode_temp(2500), 0x4A89);
+        assert_eq!(encode_hum(5000), 0x6400);
+    }
+
+    #[test]
+    fn test_compensation_round_trip() {
+        for temp in [0, 2500, -1000, 8500, -4000] {
+            let raw = encode_temp(temp).to_le_bytes();
+            let expectations = [
+                Transaction::write(0x52, vec![0x13, raw[0], raw[1]]),
+                Transaction::write_read(0x52, vec![0x30], vec![raw[0], raw[1], 0x00, 0x00]),
+            ];
+            let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+            device.set_temp(temp).unwrap();
+            let (read_back, _) = device.temp_and_hum().unwrap();
+            assert!(
+                (temp - read_back).abs() <= 2,
+                "{} read back as {}",
+                temp,
+                read_back
+            );
+            device.release().done();
+        }
+
+        for hum in [0, 5000, 9999, 10000] {
+            let raw = encode_hum(hum).to_le_bytes();
+            let expectations = [
+                Transaction::write(0x52, vec![0x15, raw[0], raw[1]]),
+                Transaction::write_read(0x52, vec![0x30], vec![0x89, 0x4A, raw[0], raw[1]]),
+            ];
+            let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+            device.set_hum(hum).unwrap();
+            let (_, read_back) = device.temp_and_hum().unwrap();
+            assert!(
+                hum.abs_diff(read_back) <= 2,
+                "{} read back as {}",
+                hum,
+                read_back
+            );
+            device.release().done();
+        }
+    }
+
+    #[test]
+    fn test_read_data_temp_and_rh() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x30], vec![0x89, 0x4A]),
+            Transaction::write_read(0x52, vec![0x32], vec![0x00, 0x64]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(device.read_data_temp().unwrap(), 2499);
+        assert_eq!(device.read_data_rh().unwrap(), 5000);
+        device.release().done();
+    }
+
+    struct FixedSensor {
+        temp: i16,
+        hum: u16,
+    }
+
+    impl TemperatureHumiditySensor for FixedSensor {
+        fn read_temp_scaled_100(&mut self) -> i16 {
+            self.temp
+        }
+
+        fn read_hum_scaled_100(&mut self) -> u16 {
+            self.hum
+        }
+    }
+
+    #[test]
+    fn test_set_compensation_from() {
+        let expectations = [Transaction::write(0x52, vec![0x13, 0x89, 0x4A, 0x00, 0x64])];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut sensor = FixedSensor {
+            temp: 2500,
+            hum: 5000,
+        };
+        device.set_compensation_from(&mut sensor).unwrap();
+
+        sensor.hum = 10001;
+        assert!(matches!(
+            device.set_compensation_from(&mut sensor),
+            Err(Error::OutOfRange { value: 10001, .. })
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_compensation_inputs() {
+        for (temp, hum) in [(-4000, 0), (2500, 5000), (8500, 10000)] {
+            let t = encode_temp(temp).to_le_bytes();
+            let h = encode_hum(hum).to_le_bytes();
+            let expectations = [
+                Transaction::write(0x52, vec![0x13, t[0], t[1], h[0], h[1]]),
+                Transaction::write_read(0x52, vec![0x13], vec![t[0], t[1]]),
+                Transaction::write_read(0x52, vec![0x15], vec![h[0], h[1]]),
+            ];
+            let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+            device.set_temp_and_hum(temp, hum).unwrap();
+            let read_temp = device.read_temp_in().unwrap();
+            let read_hum = device.read_hum_in().unwrap();
+            assert!(
+                temp.abs_diff(read_temp) <= 2,
+                "{temp} read back as {read_temp}"
+            );
+            assert!(hum.abs_diff(read_hum) <= 2, "{hum} read back as {read_hum}");
+            device.release().done();
+        }
+    }
+
+    #[test]
+    fn test_set_temp_and_hum() {
+        let expectations = [Transaction::write(0x52, vec![0x13, 0x89, 0x4A, 0x00, 0x64])];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.set_temp_and_hum(2500, 5000).unwrap();
+        device.release().done();
+    }
+
+    #[test]
+    fn test_compensation_out_of_range() {
+        let mut device = Ens160::new(Mock::new(&[]), 0x52);
+        assert!(matches!(
+            device.set_temp(-4001),
+            Err(Error::OutOfRange {
+                field: "temperature",
+                value: -4001,
+                min: -4000,
+                max: 8500
+            })
+        ));
+        assert!(matches!(
+            device.set_temp(8501),
+            Err(Error::OutOfRange { value: 8501, .. })
+        ));
+        assert!(matches!(
+            device.set_hum(10001),
+            Err(Error::OutOfRange {
+                field: "humidity",
+                value: 10001,
+                min: 0,
+                max: 10000
+            })
+        ));
+        assert!(matches!(
+            device.set_temp_and_hum(2500, 10001),
+            Err(Error::OutOfRange { value: 10001, .. })
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_wait_for_data_ready() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000010]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+        let status = device.wait_for_data_ready(&mut delay, 100).unwrap();
+        assert!(status.data_is_ready());
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_wait_for_data_ready_timeout() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ms(10),
+            DelayTransaction::delay_ms(10),
+        ]);
+        assert!(matches!(
+            device.wait_for_data_ready(&mut delay, 20),
+            Err(Error::WaitTimeout)
+        ));
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_wait_for_data_ready_without_timeout() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000010]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ms(10),
+            DelayTransaction::delay_ms(10),
+        ]);
+        let status = device.wait_for_data_ready(&mut delay, u32::MAX).unwrap();
+        assert!(status.data_is_ready());
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_wait_ready_with_delay() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000100]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000110]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000010]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ms(5),
+            DelayTransaction::delay_ms(5),
+            DelayTransaction::delay_ms(5),
+        ]);
+        device.wait_ready_with_delay(&mut delay, 5, 50).unwrap();
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_wait_ready_with_delay_timeout() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000110]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000100]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut delay =
+            CheckedDelay::new(&[DelayTransaction::delay_ms(1), DelayTransaction::delay_ms(1)]);
+        assert!(matches!(
+            device.wait_ready_with_delay(&mut delay, 0, 2),
+            Err(Error::WaitTimeout)
+        ));
+        device.release().done();
+        delay.done();
+
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b00000000]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ms(u32::MAX - 1),
+            DelayTransaction::delay_ms(u32::MAX - 1),
+        ]);
+        assert!(matches!(
+            device.wait_ready_with_delay(&mut delay, u32::MAX - 1, u32::MAX),
+            Err(Error::WaitTimeout)
+        ));
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_initialize() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0xF0]),
+            Transaction::write_read(0x52, vec![0x00], vec![0x60, 0x01]),
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write(0x52, vec![0x12, 0xCC]),
+            Transaction::write(0x52, vec![0x13, 0x89, 0x4A]),
+            Transaction::write(0x52, vec![0x10, 0x02]),
+        ];
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+        let device = Ens160::new(Mock::new(&expectations), 0x52)
+            .initialize(&mut delay, Some(2500), None)
+            .unwrap();
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_probe() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x00], vec![0x60, 0x01]),
+            Transaction::write_read(0x52, vec![0x00], vec![0x61, 0x01]),
+            Transaction::write_read(0x52, vec![0x00], vec![0x00, 0x00])
+                .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert!(device.probe().unwrap());
+        assert!(!device.probe().unwrap());
+        assert!(matches!(
+            device.probe(),
+            Err(Error::I2c(ErrorKind::NoAcknowledge(_)))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_reset_and_initialize() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0xF0]),
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write(0x52, vec![0x12, 0xCC]),
+        ];
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+        let device = Ens160::new(Mock::new(&expectations), 0x52)
+            .reset_and_initialize(&mut delay)
+            .unwrap();
+        device.release().done();
+        delay.done();
+    }
+
+    #[cfg(feature = "fixed")]
+    #[test]
+    fn test_fixed_compensation_round_trip() {
+        use fixed::types::{I12F4, U7F9};
+
+        let expectations = [
+            Transaction::write(0x52, vec![0x13, 0xA9, 0x4A]),
+            Transaction::write_read(0x52, vec![0x30], vec![0xA9, 0x4A]),
+            Transaction::write_read(0x52, vec![0x32], vec![0x80, 0x64]),
+            Transaction::write(0x52, vec![0x13, 0x49, 0x3A]),
+            Transaction::write_read(0x52, vec![0x30], vec![0x49, 0x3A]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let celsius = I12F4::from_num(25.5);
+        device.set_temp_fixed(celsius).unwrap();
+        assert_eq!(device.temp_celsius_fixed().unwrap(), celsius);
+        assert_eq!(
+            device.humidity_percent_fixed().unwrap(),
+            U7F9::from_num(50.25)
+        );
+        let celsius = I12F4::from_num(-40);
+        device.set_temp_fixed(celsius).unwrap();
+        assert_eq!(device.temp_celsius_fixed().unwrap(), celsius);
+        assert!(matches!(
+            device.set_temp_fixed(I12F4::from_num(85.0625)),
+            Err(Error::OutOfRange { value: 8506, .. })
+        ));
+        device.release().done();
+    }
+
+    #[cfg(any(feature = "libm", feature = "hardware-float"))]
+    #[test]
+    fn test_f32_decoding_matches_integer() {
+        use super::{decode_hum_f32, decode_temp_f32};
+
+        for raw in [0x3A49, 0x4AA9, 0x4AAA, 0x5920] {
+            let expectations = [Transaction::write_read(
+                0x52,
+                vec![0x30],
+                vec![raw as u8, (raw >> 8) as u8, raw as u8, (raw >> 8) as u8],
+            )];
+            let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+            let (temp, hum) = device.temp_and_hum().unwrap();
+            assert!((decode_temp_f32(raw) - f32::from(temp) / 100.0).abs() < 0.01);
+            assert!((decode_hum_f32(raw) - f32::from(hum) / 100.0).abs() < 0.01);
+            device.release().done();
+        }
+    }
+
+    #[cfg(feature = "libm")]
+    #[test]
+    fn test_f32_compensation_round_trip() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x13, 0xA9, 0x4A]),
+            Transaction::write(0x52, vec![0x15, 0x80, 0x64]),
+            Transaction::write_read(0x52, vec![0x30], vec![0xA9, 0x4A]),
+            Transaction::write_read(0x52, vec![0x32], vec![0x80, 0x64]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.set_temp_f32(25.5).unwrap();
+        device.set_hum_f32(50.25).unwrap();
+        assert!((device.temp_celsius_f32().unwrap() - 25.5).abs() < 0.01);
+        assert!((device.humidity_percent_f32().unwrap() - 50.25).abs() < 0.01);
+        assert!(matches!(
+            device.set_temp_f32(85.01),
+            Err(Error::OutOfRange { value: 8501, .. })
+        ));
+        assert!(matches!(
+            device.set_hum_f32(f32::NAN),
+            Err(Error::OutOfRange { .. })
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_interrupt_config() {
+        let configs = [
+            InterruptConfig::data_ready_push_pull_active_high(),
+            InterruptConfig::data_ready_open_drain_active_low(),
+            InterruptConfig::gpr_ready_push_pull_active_high(),
+            InterruptConfig::disabled(),
+        ];
+        let expectations: Vec<_> = configs
+            .iter()
+            .flat_map(|config| {
+                let byte = config.finish().0;
+                [
+                    Transaction::write(0x52, vec![0x11, byte]),
+                    Transaction::write_read(0x52, vec![0x11], vec![byte]),
+                ]
+            })
+            .collect();
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        for config in configs {
+            let byte = config.finish().0;
+            device.set_interrupt_config(config).unwrap();
+            let read_back = device.read_interrupt_config().unwrap();
+            assert_eq!(read_back.finish().0, byte);
+        }
+        device.release().done();
+    }
+
+    #[test]
+    fn test_const_new() {
+        struct FakeI2c;
+        const ENS160: Ens160<FakeI2c> = Ens160::new(FakeI2c, 0x52);
+        assert_eq!(ENS160.address(), 0x52);
+    }
+
+    #[test]
+    fn test_read_operation_mode_validated() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x10], vec![0x02]),
+            Transaction::write_read(0x52, vec![0x10], vec![0x01]),
+            Transaction::write_read(0x52, vec![0x10], vec![0x00]).with_error(ErrorKind::Other),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device
+            .read_operation_mode_validated(OperationMode::Standard)
+            .unwrap();
+        assert!(matches!(
+            device.read_operation_mode_validated(OperationMode::Standard),
+            Err(Error::UnexpectedMode {
+                actual: OperationMode::Idle,
+                expected: OperationMode::Standard
+            })
+        ));
+        assert!(matches!(
+            device.read_operation_mode_validated(OperationMode::Standard),
+            Err(Error::I2c(ErrorKind::Other))
+        ));
+        device.release().done();
+    }
+
+    #[cfg(feature = "i2c-transaction")]
+    #[test]
+    fn test_read_register_combined() {
+        let expectations = [
+            Transaction::transaction_start(0x52),
+            Transaction::write(0x52, vec![0x00]),
+            Transaction::read(0x52, vec![0x60, 0x01]),
+            Transaction::transaction_end(0x52),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(
+            device.read_register_combined::<2>(0x00).unwrap(),
+            [0x60, 0x01]
+        );
+        device.release().done();
+    }
+
+    #[test]
+    fn test_soft_reset() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write(0x52, vec![0x12, 0xCC]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52).idle().unwrap();
+        device.soft_reset().unwrap();
+        device.release().done();
+    }
+
+    #[test]
+    fn test_firmware_version_is_supported() {
+        let mut expectations = vec![Transaction::write(0x52, vec![0x10, 0x01])];
+        for _ in 0..5 {
+            expectations.push(Transaction::write(0x52, vec![0x12, 0x0E]));
+            expectations.push(Transaction::write_read(
+                0x52,
+                vec![0x48],
+                vec![5, 4, 6, 0, 0, 0, 0, 0],
+            ));
+        }
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52).idle().unwrap();
+        assert!(device
+            .firmware_version_is_supported(FirmwareVersion::from((5, 4, 6)))
+            .unwrap());
+        assert!(!device
+            .firmware_version_is_supported(FirmwareVersion::from((5, 5, 0)))
+            .unwrap());
+        device
+            .assert_firmware_version(FirmwareVersion::from((5, 4, 6)))
+            .unwrap();
+        device
+            .assert_firmware_version(FirmwareVersion::from((4, 9, 9)))
+            .unwrap();
+        assert!(matches!(
+            device.assert_firmware_version(FirmwareVersion::from((6, 0, 0))),
+            Err(Error::FirmwareTooOld { actual, required })
+                if actual == FirmwareVersion::from((5, 4, 6))
+                    && required == FirmwareVersion::from((6, 0, 0))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_verify_communication() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write_read(0x52, vec![0x00], vec![0x60, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00]),
+            Transaction::write_read(0x52, vec![0x00], vec![0x61, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x00])
+                .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.verify_communication().unwrap();
+        assert!(matches!(
+            device.verify_communication(),
+            Err(Error::InvalidPartId(0x0161))
+        ));
+        assert!(matches!(
+            device.verify_communication(),
+            Err(Error::I2c(ErrorKind::NoAcknowledge(_)))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_accessors() {
+        let expectations = [Transaction::write(0x53, vec![0x10, 0x01])];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x53);
+        assert_eq!(device.address(), 0x53);
+        let bus: *const Mock = device.i2c();
+        assert!(core::ptr::eq(bus, device.i2c_mut()));
+        device.i2c_mut().write(0x53, &[0x10, 0x01]).unwrap();
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_operation_mode() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x10], vec![0x02]),
+            Transaction::write_read(0x52, vec![0x10], vec![0x07]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(
+            device.read_operation_mode().unwrap(),
+            OperationMode::Standard
+        );
+        assert!(matches!(
+            device.read_operation_mode(),
+            Err(Error::InvalidMode(0x07))
+        ));
+        device.release().done();
+    }
+
+    #[cfg(feature = "nb")]
+    #[test]
+    fn test_try_read() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x20], vec![0b10000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b10000010]),
+            Transaction::write_read(0x52, vec![0x24], vec![0x9C, 0x01]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b10000000]),
+            Transaction::write_read(0x52, vec![0x20], vec![0b10000010]),
+            Transaction::write_read(0x52, vec![0x21], vec![0x02]),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000000, 0x02, 0x57, 0x00, 0x9C, 0x01],
+            ),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert!(matches!(device.try_read_eco2(), Err(nb::Error::WouldBlock)));
+        assert_eq!(device.try_read_eco2().unwrap().ppm(), 412);
+        assert!(matches!(device.try_read_tvoc(), Err(nb::Error::WouldBlock)));
+        assert_eq!(device.try_read_aqi().unwrap(), AirQualityIndex::Good);
+        assert!(matches!(device.try_read_all(), Err(nb::Error::WouldBlock)));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_output_burst_offsets() {
+        let expectations = [
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x03, 0x2C, 0x01, 0x20, 0x03],
+            ),
+            Transaction::write_read(0x52, vec![0x20], vec![0b10000010]),
+            Transaction::write_read(0x52, vec![0x21], vec![0x03]),
+            Transaction::write_read(0x52, vec![0x22], vec![0x2C, 0x01]),
+            Transaction::write_read(0x52, vec![0x24], vec![0x20, 0x03]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let bundle = device.read_all().unwrap();
+        assert_eq!(bundle.status.0, device.status().unwrap().0);
+        assert_eq!(bundle.aqi.value, device.airquality_index().unwrap());
+        assert_eq!(bundle.tvoc.value, device.tvoc().unwrap());
+        assert_eq!(bundle.eco2.value, device.eco2().unwrap());
+        device.release().done();
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn test_log_register_access() {
+        use std::sync::Mutex;
+
+        static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        struct TestLogger;
+
+        impl log::Log for TestLogger {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                RECORDS.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        log::set_logger(&TestLogger).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x38], vec![0x5A]),
+            Transaction::write(0x52, vec![0x37, 0x01]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.read_register_raw::<1>(0x38).unwrap();
+        device.write_register_raw([0x37, 0x01]).unwrap();
+        device.release().done();
+
+        let records = RECORDS.lock().unwrap();
+        assert!(records.contains(&"read register 0x38 -> [5A]".to_string()));
+        assert!(records.contains(&"write register 0x37 data=[01]".to_string()));
+    }
+
+    #[test]
+    fn test_debug_and_clone() {
+        let device = Ens160::new(0u8, 0x53);
+        assert_eq!(
+            format!("{:?}", device),
+            "Ens160 { i2c: 0, address: 0x53, ventilation_eco2: ECo2(1000), ventilation_tvoc: Tvoc(660), alarm_config: None }"
+        );
+        let device = device.with_alarm_config(AlarmConfig::default());
+        assert!(format!("{:?}", device)
+            .contains("alarm_config: Some(AlarmConfig { eco2_warn_ppm: 1000,"));
+        let clone = device.clone();
+        assert_eq!(clone.address(), 0x53);
+    }
+
+    #[test]
+    fn test_sample() {
+        let expectations = [
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000000, 0x02, 0x57, 0x00, 0x9C, 0x01],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000110, 0x02, 0x57, 0x00, 0x9C, 0x01],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x02, 0x57, 0x00, 0x9C, 0x01],
+            ),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert!(device.sample().unwrap().is_none());
+        assert!(device.sample().unwrap().is_none());
+        let bundle = device.sample().unwrap().unwrap();
+        assert_eq!(bundle.aqi.value, AirQualityIndex::Good);
+        assert_eq!(bundle.eco2.ppm(), 412);
+        device.release().done();
+    }
+
+    #[test]
+    fn test_baseline() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x40, 1, 2, 3, 4, 5, 6, 7, 8]),
+            Transaction::write_read(0x52, vec![0x48], vec![8, 7, 6, 5, 4, 3, 2, 1]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52).idle().unwrap();
+        device.set_baseline(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(device.get_baseline().unwrap(), [8, 7, 6, 5, 4, 3, 2, 1]);
+        device.release().done();
+    }
+
+    #[test]
+    fn test_deep_sleep_and_release() {
+        let expectations = [Transaction::write(0x52, vec![0x10, 0x00])];
+        let (result, mut i2c) =
+            Ens160::new(Mock::new(&expectations), 0x52).deep_sleep_and_release();
+        assert!(result.is_ok());
+        i2c.done();
+
+        let expectations =
+            [Transaction::write(0x52, vec![0x10, 0x00]).with_error(ErrorKind::Other)];
+        let (result, mut i2c) =
+            Ens160::new(Mock::new(&expectations), 0x52).deep_sleep_and_release();
+        assert!(matches!(result, Err(Error::I2c(ErrorKind::Other))));
+        i2c.done();
+    }
+
+    #[test]
+    fn test_from_reset() {
+        let expectations = [Transaction::write(0x53, vec![0x10, 0xF0])];
+        let mut delay = CheckedDelay::new(&[DelayTransaction::delay_ms(10)]);
+        let device = Ens160::from_reset(Mock::new(&expectations), 0x53, &mut delay).unwrap();
+        assert_eq!(device.address(), 0x53);
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_write_gpr() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x40, 1, 2, 3, 4, 5, 6, 7, 8]),
+            Transaction::write(0x52, vec![0x47, 0xAB]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.write_gpr(&[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        device.write_gpr_byte(7, 0xAB).unwrap();
+        assert!(matches!(
+            device.write_gpr_byte(8, 0xAB),
+            Err(Error::InvalidIndex(8))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_gpr() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x48], vec![1, 2, 3, 4, 5, 6, 7, 8]),
+            Transaction::write_read(0x52, vec![0x4B], vec![4]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(device.read_gpr().unwrap(), [1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(device.read_gpr_byte(3).unwrap(), 4);
+        assert!(matches!(
+            device.read_gpr_byte(8),
+            Err(Error::InvalidIndex(8))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_gpr_byte_registers() {
+        use crate::{
+            ENS160_GPR_READ_REG_0, ENS160_GPR_READ_REG_7, ENS160_GPR_WRITE_REG_0,
+            ENS160_GPR_WRITE_REG_7,
+        };
+
+        let expectations = [
+            Transaction::write(0x52, vec![ENS160_GPR_WRITE_REG_0, 0x12]),
+            Transaction::write(0x52, vec![ENS160_GPR_WRITE_REG_7, 0x34]),
+            Transaction::write_read(0x52, vec![ENS160_GPR_READ_REG_0], vec![0x56]),
+            Transaction::write_read(0x52, vec![ENS160_GPR_READ_REG_7], vec![0x78]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.write_gpr_byte(0, 0x12).unwrap();
+        device.write_gpr_byte(7, 0x34).unwrap();
+        assert_eq!(device.read_gpr_byte(0).unwrap(), 0x56);
+        assert_eq!(device.read_gpr_byte(7).unwrap(), 0x78);
+        assert!(matches!(
+            device.write_gpr_byte(8, 0),
+            Err(Error::InvalidIndex(8))
+        ));
+        assert!(matches!(
+            device.read_gpr_byte(u8::MAX),
+            Err(Error::InvalidIndex(u8::MAX))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_raw_resistances() {
+        let expectations = [Transaction::write_read(
+            0x52,
+            vec![0x48],
+            vec![0x34, 0x12, 0xFF, 0x00, 0x00, 0xFF, 0x01, 0x00],
+        )];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(
+            device.read_raw_resistances().unwrap(),
+            [0x1234, 0x00FF, 0xFF00, 0x0001]
+        );
+        device.release().done();
+    }
+
+    #[test]
+    fn test_raw_register_access() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x11, 0x23]),
+            Transaction::write_read(0x52, vec![0x11], vec![0x23]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.write_register_raw([0x11, 0x23]).unwrap();
+        assert_eq!(device.read_register_raw::<1>(0x11).unwrap(), [0x23]);
+        device.release().done();
+    }
+
+    #[test]
+    fn test_verify_part_id() {
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x00], vec![0x60, 0x01]),
+            Transaction::write_read(0x52, vec![0x00], vec![0x61, 0x01]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        device.verify_part_id().unwrap();
+        assert!(matches!(
+            device.verify_part_id(),
+            Err(Error::InvalidPartId(0x0161))
+        ));
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_etoh() {
+        let expectations = [Transaction::write_read(0x52, vec![0x22], vec![0x2C, 0x01])];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(device.read_etoh().unwrap(), 300);
+        device.release().done();
+    }
+
+    #[test]
+    fn test_misr() {
+        assert_eq!(compute_misr(&[]), 0x00);
+        assert_eq!(compute_misr(&[0x01]), 0x01);
+        assert_eq!(compute_misr(&[0x80, 0x00]), 0x1D);
+
+        let expectations = [
+            Transaction::write_read(0x52, vec![0x38], vec![0x1D]),
+            Transaction::write_read(0x52, vec![0x38], vec![0x1D]),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert!(device.verify_last_read(&[0x80, 0x00]).unwrap());
+        assert!(!device.verify_last_read(&[0x80, 0x01]).unwrap());
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_status_and_eco2() {
+        let expectations = [Transaction::write_read(
+            0x52,
+            vec![0x20],
+            vec![0b10000010, 0x05, 0xFF, 0xFF, 0x9C, 0x01],
+        )];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let (status, eco2) = device.read_status_and_eco2().unwrap();
+        assert_eq!(status.0, 0b10000010);
+        assert_eq!(eco2.ppm(), 412);
+        device.release().done();
+    }
+
+    #[test]
+    fn test_take_n_samples() {
+        let status = |status: u8| Transaction::write_read(0x52, vec![0x20], vec![status]);
+        let burst = |status: u8, eco2: u8| {
+            Transaction::write_read(0x52, vec![0x20], vec![status, 0x01, 0x0A, 0x00, eco2, 0x02])
+        };
+        let expectations = [
+            status(0b10000000),
+            status(0b10000010),
+            burst(0b10000010, 0x00),
+            status(0b10000110),
+            burst(0b10000110, 0xFF),
+            status(0b10000010),
+            burst(0b10000010, 0x64),
+        ];
+        let mut delay = CheckedDelay::new(&[
+            DelayTransaction::delay_ms(10),
+            DelayTransaction::delay_ms(100),
+            DelayTransaction::delay_ms(100),
+        ]);
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let stats = device.take_n_samples(3, 100, &mut delay).unwrap();
+        assert_eq!(stats.count(), 2);
+        assert_eq!(stats.skipped(), 1);
+        assert_eq!(stats.eco2_min(), Some(ECo2::from(512)));
+        assert_eq!(stats.eco2_max(), Some(ECo2::from(612)));
+        assert_eq!(stats.tvoc_mean(), Some(Tvoc::from(10)));
+        device.release().done();
+        delay.done();
+    }
+
+    #[test]
+    fn test_alarms_triggered() {
+        let expectations = [
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x05, 0x98, 0x08, 0xD0, 0x07],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x05, 0x98, 0x08, 0xD0, 0x07],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x04, 0x94, 0x02, 0xE8, 0x03],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000110, 0x05, 0x98, 0x08, 0xD0, 0x07],
+            ),
+        ];
+        let levels = (AlarmLevel::Ok, AlarmLevel::Ok);
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert_eq!(device.alarm_config(), None);
+        assert_eq!(
+            device.alarms_triggered(levels).unwrap(),
+            (AlarmLevel::Ok, AlarmLevel::Ok)
+        );
+        let mut device = device.with_alarm_config(AlarmConfig::default());
+        assert_eq!(
+            device.alarms_triggered(levels).unwrap(),
+            (AlarmLevel::Alarm, AlarmLevel::Alarm)
+        );
+        assert_eq!(
+            device.alarms_triggered(levels).unwrap(),
+            (AlarmLevel::Warning, AlarmLevel::Warning)
+        );
+        assert_eq!(
+            device.alarms_triggered(levels).unwrap(),
+            (AlarmLevel::Ok, AlarmLevel::Ok)
+        );
+        device.release().done();
+    }
+
+    #[test]
+    fn test_ventilation_needed() {
+        let expectations = [
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x03, 0x94, 0x02, 0xE8, 0x03],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x03, 0x94, 0x02, 0xE9, 0x03],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x04, 0x95, 0x02, 0xE8, 0x03],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000110, 0x05, 0x00, 0x10, 0x00, 0x10],
+            ),
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x02, 0x94, 0x02, 0xE8, 0x03],
+            ),
+        ];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        assert!(!device.ventilation_needed().unwrap());
+        assert!(device.ventilation_needed().unwrap());
+        assert!(device.ventilation_needed().unwrap());
+        assert!(!device.ventilation_needed().unwrap());
+        device.set_ventilation_thresholds(800, 1000);
+        assert!(device.ventilation_needed().unwrap());
+        device.release().done();
+    }
+
+    #[test]
+    fn test_read_all() {
+        let expectations = [Transaction::write_read(
+            0x52,
+            vec![0x20],
+            vec![0b10000010, 0x02, 0x57, 0x00, 0x9C, 0x01],
+        )];
+        let mut device = Ens160::new(Mock::new(&expectations), 0x52);
+        let bundle = device.read_all().unwrap();
+        assert!(bundle.status.data_is_ready());
+        assert!(bundle.is_valid());
+        assert_eq!(bundle.aqi.value, AirQualityIndex::Good);
+        assert_eq!(bundle.tvoc.ppb(), 87);
+        assert_eq!(bundle.eco2.ppm(), 412);
+        device.release().done();
+    }
 
-    async fn write_register<const N: usize>(&mut self, buffer: [u8; N]) -> Result<(), E> {
-        self.i2c.write(self.address, &buffer).await
+    #[test]
+    fn test_mode_transitions() {
+        let expectations = [
+            Transaction::write(0x52, vec![0x10, 0x01]),
+            Transaction::write(0x52, vec![0x12, 0x0E]),
+            Transaction::write_read(0x52, vec![0x48], vec![5, 4, 3, 0, 0, 0, 0, 0]),
+            Transaction::write(0x52, vec![0x10, 0x02]),
+        ];
+        let device = Ens160::new(Mock::new(&expectations), 0x52);
+        let mut device = device.idle().unwrap();
+        assert_eq!(
+            device.firmware_version().unwrap(),
+            FirmwareVersion::from((5, 4, 3))
+        );
+        let device = device.operational().unwrap();
+        device.release().done();
     }
 }
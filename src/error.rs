@@ -1,12 +1,134 @@
+use crate::{FirmwareVersion, OperationMode, ENS160_PART_ID};
+
+/// Errors returned by the driver.
 #[derive(Debug)]
-pub struct AirqualityConvError(pub(crate) u16);
+pub enum Error<E> {
+    /// An error on the underlying bus.
+    I2c(E),
+    /// A value passed to the driver is outside of the range the sensor supports.
+    OutOfRange {
+        field: &'static str,
+        value: i32,
+        min: i32,
+        max: i32,
+    },
+    /// A register index passed to the driver is out of bounds.
+    InvalidIndex(u8),
+    /// The device reported a part ID other than [`ENS160_PART_ID`].
+    InvalidPartId(u16),
+    /// The `OPMODE` register holds no known [`OperationMode`].
+    InvalidMode(u8),
+    /// The sensor is in another operation mode than expected.
+    UnexpectedMode {
+        actual: OperationMode,
+        expected: OperationMode,
+    },
+    /// The firmware of the sensor is older than required.
+    FirmwareTooOld {
+        actual: FirmwareVersion,
+        required: FirmwareVersion,
+    },
+    /// The sensor didn't get ready in time.
+    WaitTimeout,
+}
+
+impl<E> From<E> for Error<E> {
+    fn from(e: E) -> Self {
+        Self::I2c(e)
+    }
+}
+
+impl<E: core::fmt::Display> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::I2c(e) => write!(f, "I2C error: {}", e),
+            Self::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(f, "{} {} out of range [{}, {}]", field, value, min, max),
+            Self::InvalidIndex(index) => write!(f, "Register index {} out of range", index),
+            Self::InvalidPartId(id) => write!(
+                f,
+                "Unexpected part ID: 0x{:04X} (expected 0x{:04X})",
+                id, ENS160_PART_ID
+            ),
+            Self::InvalidMode(mode) => write!(f, "Unknown operation mode: 0x{:02X}", mode),
+            Self::UnexpectedMode { actual, expected } => write!(
+                f,
+                "Sensor is in operation mode {:?} instead of {:?}",
+                actual, expected
+            ),
+            Self::FirmwareTooOld { actual, required } => write!(
+                f,
+                "Firmware {} is older than the required {}",
+                actual, required
+            ),
+            Self::WaitTimeout => write!(f, "Wait for data ready timed out"),
+        }
+    }
+}
 
 #[cfg(feature = "std")]
-impl std::fmt::Display for AirqualityConvError {
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for Error<E> {}
+
+#[cfg(feature = "defmt")]
+impl<E: defmt::Format> defmt::Format for Error<E> {
+    fn format(&self, f: defmt::Formatter) {
+        match self {
+            Self::I2c(e) => defmt::write!(f, "I2C error: {}", e),
+            Self::OutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => defmt::write!(f, "{=str} {} out of range [{}, {}]", field, value, min, max),
+            Self::InvalidIndex(index) => {
+                defmt::write!(f, "Register index {} out of range", index)
+            }
+            Self::InvalidPartId(id) => defmt::write!(
+                f,
+                "Unexpected part ID: {=u16:#06x} (expected {=u16:#06x})",
+                id,
+                ENS160_PART_ID
+            ),
+            Self::InvalidMode(mode) => {
+                defmt::write!(f, "Unknown operation mode: {=u8:#04x}", mode)
+            }
+            Self::UnexpectedMode { actual, expected } => defmt::write!(
+                f,
+                "Sensor is in operation mode {} instead of {}",
+                actual,
+                expected
+            ),
+            Self::FirmwareTooOld { actual, required } => defmt::write!(
+                f,
+                "Firmware {} is older than the required {}",
+                actual,
+                required
+            ),
+            Self::WaitTimeout => defmt::write!(f, "Wait for data ready timed out"),
+        }
+    }
+}
+
+/// Error converting an eCO2 value below 400 ppm into an [`AirQualityIndex`](crate::AirQualityIndex).
+#[derive(Debug)]
+pub struct AirqualityConvError(pub(crate) u16);
+
+impl AirqualityConvError {
+    /// Returns the eCO2 value in ppm which couldn't be converted.
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for AirqualityConvError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
-            "{} is no valid qirquality value. Values start from 400.",
+            "eCO2 value {} ppm is below the valid range for AQI classification",
             self.0
         )
     }
@@ -14,3 +136,178 @@ impl std::fmt::Display for AirqualityConvError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for AirqualityConvError {}
+
+/// Error converting a TVOC value above 65000 ppb into an [`AirQualityIndex`](crate::AirQualityIndex).
+#[derive(Debug)]
+pub struct TvocConvError(pub(crate) u16);
+
+impl TvocConvError {
+    /// Returns the TVOC value in ppb which couldn't be converted.
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for TvocConvError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "TVOC value {} ppb is above the valid range for AQI classification",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TvocConvError {}
+
+/// Error converting a byte other than 0x52 or 0x53 into an [`Address`](crate::Address).
+#[derive(Debug)]
+pub struct InvalidAddressError(pub(crate) u8);
+
+impl InvalidAddressError {
+    /// Returns the address which couldn't be converted.
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for InvalidAddressError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "0x{:02X} is no valid ENS160 address. Valid addresses are 0x52 and 0x53.",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidAddressError {}
+
+/// Error converting a byte outside of 1 to 5 into an [`AirQualityIndex`](crate::AirQualityIndex).
+#[derive(Debug)]
+pub struct InvalidAqiError(pub(crate) u8);
+
+impl InvalidAqiError {
+    /// Returns the index which couldn't be converted.
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for InvalidAqiError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} is no valid air quality index. Valid indices are 1 to 5.",
+            self.0
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidAqiError {}
+
+/// Error converting an unknown byte into an [`OperationMode`].
+#[derive(Debug)]
+pub struct InvalidModeError(pub(crate) u8);
+
+impl InvalidModeError {
+    /// Returns the byte which couldn't be converted.
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl core::fmt::Display for InvalidModeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{:02X} is no valid ENS160 operation mode", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidModeError {}
+
+#[cfg(all(test, feature = "std"))]
+mod test {
+
+    use super::{AirqualityConvError, Error, InvalidAqiError, InvalidModeError, TvocConvError};
+    use crate::{FirmwareVersion, OperationMode};
+
+    #[test]
+    fn test_error_display() {
+        let error: Error<&str> = Error::from("nack");
+        assert!(matches!(error, Error::I2c("nack")));
+        assert_eq!(error.to_string(), "I2C error: nack");
+
+        let error: Error<&str> = Error::OutOfRange {
+            field: "humidity",
+            value: 10001,
+            min: 0,
+            max: 10000,
+        };
+        assert_eq!(error.to_string(), "humidity 10001 out of range [0, 10000]");
+
+        let error: Error<&str> = Error::InvalidIndex(8);
+        assert_eq!(error.to_string(), "Register index 8 out of range");
+
+        let error: Error<&str> = Error::InvalidPartId(0x1234);
+        assert_eq!(
+            error.to_string(),
+            "Unexpected part ID: 0x1234 (expected 0x0160)"
+        );
+
+        let error: Error<&str> = Error::InvalidMode(0x07);
+        assert_eq!(error.to_string(), "Unknown operation mode: 0x07");
+
+        let error: Error<&str> = Error::UnexpectedMode {
+            actual: OperationMode::Idle,
+            expected: OperationMode::Standard,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Sensor is in operation mode Idle instead of Standard"
+        );
+
+        let error: Error<&str> = Error::FirmwareTooOld {
+            actual: FirmwareVersion::from((5, 4, 6)),
+            required: FirmwareVersion::from((6, 0, 0)),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Firmware 5.4.6 is older than the required 6.0.0"
+        );
+
+        let error: Error<&str> = Error::WaitTimeout;
+        assert_eq!(error.to_string(), "Wait for data ready timed out");
+    }
+
+    #[test]
+    fn test_conv_error_display() {
+        let error = AirqualityConvError(399);
+        assert_eq!(error.value(), 399);
+        assert_eq!(
+            error.to_string(),
+            "eCO2 value 399 ppm is below the valid range for AQI classification"
+        );
+
+        let error = TvocConvError(65001);
+        assert_eq!(error.value(), 65001);
+        assert_eq!(
+            error.to_string(),
+            "TVOC value 65001 ppb is above the valid range for AQI classification"
+        );
+
+        let error = InvalidAqiError(6);
+        assert_eq!(error.value(), 6);
+        assert_eq!(
+            error.to_string(),
+            "6 is no valid air quality index. Valid indices are 1 to 5."
+        );
+
+        let error = InvalidModeError(0x07);
+        assert_eq!(error.value(), 0x07);
+        assert_eq!(error.to_string(), "0x07 is no valid ENS160 operation mode");
+    }
+}
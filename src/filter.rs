@@ -0,0 +1,155 @@
+use crate::{ECo2, MeasurementBundle, SensorReading, Tvoc};
+
+/// Exponential moving average smoothing eCO2 and TVOC measurements.
+///
+/// The averages are kept scaled by 100 in integers and every step is rounded to the nearest
+/// value away from zero, so they converge to a constant input for every smoothing factor.
+#[derive(Debug, Clone)]
+pub struct ExponentialMovingAverage {
+    alpha_percent: u8,
+    eco2: u32,
+    tvoc: u32,
+    initialized: bool,
+}
+
+impl ExponentialMovingAverage {
+    /// Creates a filter with the smoothing factor `alpha_percent / 100`, clamped to 1 to 100.
+    ///
+    /// Small values smooth stronger, 100 passes the measurements through unchanged.
+    pub const fn new(alpha_percent: u8) -> Self {
+        let alpha_percent = if alpha_percent == 0 {
+            1
+        } else if alpha_percent > 100 {
+            100
+        } else {
+            alpha_percent
+        };
+        Self {
+            alpha_percent,
+            eco2: 0,
+            tvoc: 0,
+            initialized: false,
+        }
+    }
+
+    /// Returns the smoothing factor in percent.
+    pub fn alpha_percent(&self) -> u8 {
+        self.alpha_percent
+    }
+
+    /// Adds a measurement and returns it with the smoothed eCO2 and TVOC concentrations.
+    ///
+    /// The first measurement after creation or [`ExponentialMovingAverage::reset()`] initializes
+    /// the averages. Status, air quality index and validity are taken over from `bundle`.
+    pub fn update(&mut self, bundle: &MeasurementBundle) -> MeasurementBundle {
+        let eco2 = u32::from(bundle.eco2.ppm()) * 100;
+        let tvoc = u32::from(bundle.tvoc.ppb()) * 100;
+        if self.initialized {
+            self.eco2 = self.smooth(self.eco2, eco2);
+            self.tvoc = self.smooth(self.tvoc, tvoc);
+        } else {
+            self.eco2 = eco2;
+            self.tvoc = tvoc;
+            self.initialized = true;
+        }
+
+        MeasurementBundle {
+            eco2: SensorReading {
+                value: ECo2::from(unscale(self.eco2)),
+                ..bundle.eco2
+            },
+            tvoc: SensorReading {
+                value: Tvoc::from(unscale(self.tvoc)),
+                ..bundle.tvoc
+            },
+            ..*bundle
+        }
+    }
+
+    /// Discards the averages, the next measurement initializes them again.
+    pub fn reset(&mut self) {
+        self.eco2 = 0;
+        self.tvoc = 0;
+        self.initialized = false;
+    }
+
+    fn smooth(&self, old: u32, current: u32) -> u32 {
+        // new = old + alpha * (current - old) / 100, which stays between old and current. The
+        // step is rounded half away from zero, so it only becomes 0 once the average is within
+        // half a unit of the input; truncating would stall up to 100 / alpha units short of it.
+        let scaled = i64::from(self.alpha_percent) * (i64::from(current) - i64::from(old));
+        let step = (scaled + 50 * scaled.signum()) / 100;
+        (i64::from(old) + step) as u32
+    }
+}
+
+/// Rounds an average scaled by 100, which never exceeds `u16::MAX * 100`.
+fn unscale(value: u32) -> u16 {
+    ((value + 50) / 100) as u16
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::ExponentialMovingAverage;
+    use crate::{bundle, AirQualityIndex, ECo2, Tvoc};
+
+    #[test]
+    fn test_converges() {
+        let mut ema = ExponentialMovingAverage::new(10);
+        ema.update(&bundle(400, 0));
+        let mut smoothed = bundle(0, 0);
+        for _ in 0..100 {
+            smoothed = ema.update(&bundle(1000, 500));
+        }
+        assert_eq!(smoothed.eco2.value, ECo2::from(1000));
+        assert_eq!(smoothed.tvoc.value, Tvoc::from(500));
+        assert_eq!(smoothed.aqi.value, AirQualityIndex::Good);
+
+        ema.update(&bundle(u16::MAX, u16::MAX));
+        for _ in 0..200 {
+            smoothed = ema.update(&bundle(u16::MAX, u16::MAX));
+        }
+        assert_eq!(smoothed.eco2.ppm(), u16::MAX);
+    }
+
+    #[test]
+    fn test_converges_with_smallest_alpha() {
+        let mut ema = ExponentialMovingAverage::new(1);
+        ema.update(&bundle(400, 1000));
+        let mut smoothed = bundle(0, 0);
+        for _ in 0..1000 {
+            smoothed = ema.update(&bundle(1000, 400));
+        }
+        assert_eq!(smoothed.eco2.ppm(), 1000);
+        assert_eq!(smoothed.tvoc.ppb(), 400);
+    }
+
+    #[test]
+    fn test_step_response() {
+        let mut ema = ExponentialMovingAverage::new(20);
+        assert_eq!(ema.update(&bundle(400, 100)).eco2.ppm(), 400);
+
+        let smoothed = ema.update(&bundle(1400, 1100));
+        assert_eq!(smoothed.eco2.ppm(), 600);
+        assert_eq!(smoothed.tvoc.ppb(), 300);
+        let smoothed = ema.update(&bundle(1400, 1100));
+        assert_eq!(smoothed.eco2.ppm(), 760);
+
+        let smoothed = ema.update(&bundle(400, 100));
+        assert!(smoothed.eco2.ppm() > 400 && smoothed.eco2.ppm() < 760);
+
+        ema.reset();
+        assert_eq!(ema.update(&bundle(1400, 1100)).eco2.ppm(), 1400);
+    }
+
+    #[test]
+    fn test_alpha_clamped() {
+        assert_eq!(ExponentialMovingAverage::new(0).alpha_percent(), 1);
+        assert_eq!(ExponentialMovingAverage::new(255).alpha_percent(), 100);
+
+        let mut ema = ExponentialMovingAverage::new(100);
+        ema.update(&bundle(400, 0));
+        assert_eq!(ema.update(&bundle(900, 50)).eco2.ppm(), 900);
+    }
+}
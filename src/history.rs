@@ -0,0 +1,224 @@
+use crate::{mean_u16, ECo2, MeasurementBundle, Tvoc};
+
+/// Direction in which the air quality develops.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeasurementTrend {
+    /// The concentration decreases.
+    Improving,
+    /// The concentration changes by at most the trend threshold per reading.
+    Stable,
+    /// The concentration increases.
+    Worsening,
+}
+
+/// Fixed-size history of the last `N` measurements.
+///
+/// Pushing into a full buffer overwrites the oldest measurement.
+#[derive(Debug, Clone)]
+pub struct MeasurementRingBuffer<const N: usize> {
+    buf: [Option<MeasurementBundle>; N],
+    head: usize,
+    len: usize,
+    trend_threshold: u16,
+}
+
+impl<const N: usize> MeasurementRingBuffer<N> {
+    /// Creates an empty buffer.
+    pub const fn new() -> Self {
+        const {
+            assert!(
+                N > 0,
+                "MeasurementRingBuffer needs a capacity of at least one"
+            )
+        };
+        Self {
+            buf: [None; N],
+            head: 0,
+            len: 0,
+            trend_threshold: 0,
+        }
+    }
+
+    /// Sets the change per reading up to which a trend is considered
+    /// [`MeasurementTrend::Stable`], 0 by default.
+    pub fn set_trend_threshold(&mut self, threshold: u16) {
+        self.trend_threshold = threshold;
+    }
+
+    /// Adds a measurement, overwriting the oldest one if the buffer is full.
+    pub fn push(&mut self, m: MeasurementBundle) {
+        self.buf[self.head] = Some(m);
+        self.head = (self.head + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Returns the number of stored measurements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no measurement is stored.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if `N` measurements are stored.
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Removes all measurements.
+    pub fn clear(&mut self) {
+        self.buf = [None; N];
+        self.head = 0;
+        self.len = 0;
+    }
+
+    /// Iterates over the stored measurements from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = &MeasurementBundle> {
+        let oldest = (self.head + N - self.len) % N;
+        (0..self.len).filter_map(move |i| self.buf[(oldest + i) % N].as_ref())
+    }
+
+    /// Returns the average eCO2 concentration, `None` if the buffer is empty.
+    pub fn average_eco2(&self) -> Option<ECo2> {
+        self.average(|m| m.eco2.ppm()).map(ECo2::from)
+    }
+
+    /// Returns the average TVOC concentration, `None` if the buffer is empty.
+    pub fn average_tvoc(&self) -> Option<Tvoc> {
+        self.average(|m| m.tvoc.ppb()).map(Tvoc::from)
+    }
+
+    /// Returns the trend of the eCO2 concentration, `None` if less than two measurements are
+    /// stored.
+    ///
+    /// The trend is the slope of a linear fit over all stored measurements in ppm per reading.
+    pub fn eco2_trend(&self) -> Option<MeasurementTrend> {
+        self.trend(|m| m.eco2.ppm())
+    }
+
+    /// Returns the trend of the TVOC concentration, `None` if less than two measurements are
+    /// stored.
+    ///
+    /// The trend is the slope of a linear fit over all stored measurements in ppb per reading.
+    pub fn tvoc_trend(&self) -> Option<MeasurementTrend> {
+        self.trend(|m| m.tvoc.ppb())
+    }
+
+    fn trend(&self, value: impl Fn(&MeasurementBundle) -> u16) -> Option<MeasurementTrend> {
+        if self.len < 2 {
+            return None;
+        }
+        // Least squares slope = (n Σxy - Σx Σy) / (n Σx² - (Σx)²) with x = 0..n. The sums are
+        // accumulated in i128, which can't overflow for any buffer that fits into memory.
+        let n = self.len as i128;
+        let (mut sum_x, mut sum_y, mut sum_xy, mut sum_xx) = (0i128, 0i128, 0i128, 0i128);
+        for (x, m) in self.iter().enumerate() {
+            let x = x as i128;
+            let y = i128::from(value(m));
+            sum_x += x;
+            sum_y += y;
+            sum_xy += x * y;
+            sum_xx += x * x;
+        }
+        let numerator = n * sum_xy - sum_x * sum_y;
+        let denominator = n * sum_xx - sum_x * sum_x;
+        // The denominator is positive for n >= 2, so the slope is compared without division.
+        let threshold = i128::from(self.trend_threshold) * denominator;
+        Some(if numerator > threshold {
+            MeasurementTrend::Worsening
+        } else if numerator < -threshold {
+            MeasurementTrend::Improving
+        } else {
+            MeasurementTrend::Stable
+        })
+    }
+
+    fn average(&self, value: impl Fn(&MeasurementBundle) -> u16) -> Option<u16> {
+        let sum: u64 = self.iter().map(|m| u64::from(value(m))).sum();
+        mean_u16(sum, self.len as u64)
+    }
+}
+
+impl<const N: usize> Default for MeasurementRingBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{MeasurementRingBuffer, MeasurementTrend};
+    use crate::{bundle, ECo2, Tvoc};
+
+    #[test]
+    fn test_push_and_wrap_around() {
+        let mut buffer = MeasurementRingBuffer::<3>::new();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.average_eco2(), None);
+        assert_eq!(buffer.average_tvoc(), None);
+
+        buffer.push(bundle(400, 10));
+        buffer.push(bundle(500, 20));
+        assert_eq!(buffer.len(), 2);
+        assert!(!buffer.is_full());
+        let eco2: Vec<_> = buffer.iter().map(|m| m.eco2.ppm()).collect();
+        assert_eq!(eco2, [400, 500]);
+
+        buffer.push(bundle(600, 30));
+        buffer.push(bundle(700, 40));
+        assert_eq!(buffer.len(), 3);
+        assert!(buffer.is_full());
+        let eco2: Vec<_> = buffer.iter().map(|m| m.eco2.ppm()).collect();
+        assert_eq!(eco2, [500, 600, 700]);
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_average() {
+        let mut buffer = MeasurementRingBuffer::<4>::default();
+        buffer.push(bundle(400, 10));
+        buffer.push(bundle(500, 21));
+        assert_eq!(buffer.average_eco2(), Some(ECo2::from(450)));
+        assert_eq!(buffer.average_tvoc(), Some(Tvoc::from(15)));
+
+        for _ in 0..4 {
+            buffer.push(bundle(u16::MAX, u16::MAX));
+        }
+        assert_eq!(buffer.average_eco2(), Some(ECo2::from(u16::MAX)));
+        assert_eq!(buffer.average_tvoc(), Some(Tvoc::from(u16::MAX)));
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut buffer = MeasurementRingBuffer::<5>::new();
+        buffer.push(bundle(400, 300));
+        assert_eq!(buffer.eco2_trend(), None);
+
+        for i in 1..5 {
+            buffer.push(bundle(400 + i * 50, 300 - i * 50));
+        }
+        assert_eq!(buffer.eco2_trend(), Some(MeasurementTrend::Worsening));
+        assert_eq!(buffer.tvoc_trend(), Some(MeasurementTrend::Improving));
+
+        for _ in 0..5 {
+            buffer.push(bundle(600, 100));
+        }
+        assert_eq!(buffer.eco2_trend(), Some(MeasurementTrend::Stable));
+        assert_eq!(buffer.tvoc_trend(), Some(MeasurementTrend::Stable));
+
+        for eco2 in [600, 603, 601, 605, 604] {
+            buffer.push(bundle(eco2, 100));
+        }
+        assert_eq!(buffer.eco2_trend(), Some(MeasurementTrend::Worsening));
+        buffer.set_trend_threshold(2);
+        assert_eq!(buffer.eco2_trend(), Some(MeasurementTrend::Stable));
+    }
+}
@@ -0,0 +1,230 @@
+#[cfg(feature = "blocking")]
+use embedded_hal::{
+    i2c::I2c,
+    spi::{Operation, SpiDevice},
+};
+#[cfg(feature = "async")]
+use embedded_hal_async::{
+    i2c::{I2c, SevenBitAddress},
+    spi::{Operation, SpiDevice},
+};
+
+/// Bit 0 of the SPI address byte selects a read (1) or write (0) access.
+const SPI_READ_FLAG: u8 = 0x01;
+
+/// A bus the registers of the `ENS160` can be accessed through.
+///
+/// Implemented for every I2C bus and for SPI devices wrapped in [`SpiInterface`].
+#[cfg(feature = "blocking")]
+pub trait Interface {
+    type Error;
+
+    /// Reads `buffer.len()` bytes starting at `register`.
+    ///
+    /// `address` is the I2C device address and is ignored by buses without addressing.
+    fn read_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Writes `buffer[1..]` starting at the register in `buffer[0]`.
+    ///
+    /// `address` is the I2C device address and is ignored by buses without addressing.
+    fn write_register(&mut self, address: u8, buffer: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// A bus the registers of the `ENS160` can be accessed through.
+///
+/// Implemented for every I2C bus and for SPI devices wrapped in [`SpiInterface`].
+#[cfg(feature = "async")]
+#[allow(async_fn_in_trait)]
+pub trait Interface {
+    type Error;
+
+    /// Reads `buffer.len()` bytes starting at `register`.
+    ///
+    /// `address` is the I2C device address and is ignored by buses without addressing.
+    async fn read_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error>;
+
+    /// Writes `buffer[1..]` starting at the register in `buffer[0]`.
+    ///
+    /// `address` is the I2C device address and is ignored by buses without addressing.
+    async fn write_register(&mut self, address: u8, buffer: &[u8]) -> Result<(), Self::Error>;
+}
+
+#[cfg(feature = "blocking")]
+impl<I2C> Interface for I2C
+where
+    I2C: I2c,
+{
+    type Error = I2C::Error;
+
+    fn read_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.write_read(address, &[register], buffer)
+    }
+
+    fn write_register(&mut self, address: u8, buffer: &[u8]) -> Result<(), Self::Error> {
+        self.write(address, buffer)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C> Interface for I2C
+where
+    I2C: I2c<SevenBitAddress>,
+{
+    type Error = I2C::Error;
+
+    async fn read_register(
+        &mut self,
+        address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.write_read(address, &[register], buffer).await
+    }
+
+    async fn write_register(&mut self, address: u8, buffer: &[u8]) -> Result<(), Self::Error> {
+        self.write(address, buffer).await
+    }
+}
+
+/// Wraps an SPI device so it can be used as [`Interface`] of the `ENS160`.
+///
+/// Each access starts with an address byte holding the register in bits 7:1
+/// and the read/write flag in bit 0.
+#[derive(Debug)]
+pub struct SpiInterface<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> SpiInterface<SPI> {
+    /// Creates a new SPI interface.
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Releases the underlying SPI device.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+}
+
+fn spi_address_byte(register: u8, read: bool) -> u8 {
+    let flag = if read { SPI_READ_FLAG } else { 0 };
+    (register << 1) | flag
+}
+
+#[cfg(feature = "blocking")]
+impl<SPI> Interface for SpiInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    type Error = SPI::Error;
+
+    fn read_register(
+        &mut self,
+        _address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.spi.transaction(&mut [
+            Operation::Write(&[spi_address_byte(register, true)]),
+            Operation::Read(buffer),
+        ])
+    }
+
+    fn write_register(&mut self, _address: u8, buffer: &[u8]) -> Result<(), Self::Error> {
+        let (register, data) = match buffer.split_first() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        self.spi.transaction(&mut [
+            Operation::Write(&[spi_address_byte(*register, false)]),
+            Operation::Write(data),
+        ])
+    }
+}
+
+#[cfg(feature = "async")]
+impl<SPI> Interface for SpiInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    type Error = SPI::Error;
+
+    async fn read_register(
+        &mut self,
+        _address: u8,
+        register: u8,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[spi_address_byte(register, true)]),
+                Operation::Read(buffer),
+            ])
+            .await
+    }
+
+    async fn write_register(&mut self, _address: u8, buffer: &[u8]) -> Result<(), Self::Error> {
+        let (register, data) = match buffer.split_first() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[spi_address_byte(*register, false)]),
+                Operation::Write(data),
+            ])
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod test {
+
+    use embedded_hal_mock::eh1::spi::{Mock, Transaction};
+
+    use super::{Interface, SpiInterface};
+
+    #[test]
+    fn test_spi_read_framing() {
+        let expectations = [
+            Transaction::transaction_start(),
+            Transaction::write(0x41),
+            Transaction::read_vec(vec![0x60, 0x01]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiInterface::new(Mock::new(&expectations));
+        let mut buffer = [0u8; 2];
+        spi.read_register(0, 0x20, &mut buffer).unwrap();
+        assert_eq!(buffer, [0x60, 0x01]);
+        spi.release().done();
+    }
+
+    #[test]
+    fn test_spi_write_framing() {
+        let expectations = [
+            Transaction::transaction_start(),
+            Transaction::write(0x20),
+            Transaction::write_vec(vec![0x02]),
+            Transaction::transaction_end(),
+        ];
+        let mut spi = SpiInterface::new(Mock::new(&expectations));
+        spi.write_register(0, &[0x10, 0x02]).unwrap();
+        spi.release().done();
+    }
+}
@@ -2,18 +2,77 @@
 // #![feature(generic_const_exprs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod alarm;
+mod builder;
+mod compensation;
 mod ens160_impl;
 pub mod error;
+mod filter;
+mod history;
+mod interface;
+pub mod mode;
+mod multi_sensor;
 mod registers;
+mod stats;
 
 use core::{
     convert::TryFrom,
-    ops::{Deref, DerefMut},
+    fmt,
+    iter::Sum,
+    ops::{Add, Deref, Div, Mul, Sub},
 };
 
+pub use alarm::{AlarmConfig, AlarmLevel, HysteresisConfig};
 use bitfield::bitfield;
+pub use builder::{Ens160Builder, StartupConfig};
+pub use compensation::TemperatureHumiditySensor;
 pub use ens160_impl::Ens160;
-use error::AirqualityConvError;
+use error::{
+    AirqualityConvError, InvalidAddressError, InvalidAqiError, InvalidModeError, TvocConvError,
+};
+pub use filter::ExponentialMovingAverage;
+pub use history::{MeasurementRingBuffer, MeasurementTrend};
+#[cfg(any(feature = "blocking", feature = "async"))]
+pub use interface::Interface;
+pub use interface::SpiInterface;
+pub use multi_sensor::MultiSensorBus;
+pub use registers::{
+    ENS160_GPR_READ_REG_0, ENS160_GPR_READ_REG_1, ENS160_GPR_READ_REG_2, ENS160_GPR_READ_REG_3,
+    ENS160_GPR_READ_REG_4, ENS160_GPR_READ_REG_5, ENS160_GPR_READ_REG_6, ENS160_GPR_READ_REG_7,
+    ENS160_GPR_WRITE_REG_0, ENS160_GPR_WRITE_REG_1, ENS160_GPR_WRITE_REG_2, ENS160_GPR_WRITE_REG_3,
+    ENS160_GPR_WRITE_REG_4, ENS160_GPR_WRITE_REG_5, ENS160_GPR_WRITE_REG_6, ENS160_GPR_WRITE_REG_7,
+    ENS160_PART_ID, ENS160_RESET_DELAY_MS,
+};
+pub use stats::SensorStats;
+
+/// I2C address of the sensor, selected by the level of the ADDR pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Address {
+    /// ADDR pin low.
+    Primary = 0x52,
+    /// ADDR pin high.
+    Secondary = 0x53,
+}
+
+impl From<Address> for u8 {
+    fn from(a: Address) -> u8 {
+        a as u8
+    }
+}
+
+impl TryFrom<u8> for Address {
+    type Error = InvalidAddressError;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        match v {
+            0x52 => Ok(Self::Primary),
+            0x53 => Ok(Self::Secondary),
+            _ => Err(InvalidAddressError(v)),
+        }
+    }
+}
 
 /// Commands for ENS160_COMMAND_REG.
 #[repr(u8)]
@@ -27,8 +86,10 @@ enum Command {
 }
 
 /// Operation Mode of the sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
-enum OperationMode {
+pub enum OperationMode {
     /// DEEP SLEEP mode (low power standby).
     Sleep = 0x00,
     /// IDLE mode (low-power).
@@ -39,8 +100,23 @@ enum OperationMode {
     Reset = 0xF0,
 }
 
+impl TryFrom<u8> for OperationMode {
+    type Error = InvalidModeError;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        match v {
+            0x00 => Ok(Self::Sleep),
+            0x01 => Ok(Self::Idle),
+            0x02 => Ok(Self::Standard),
+            0xF0 => Ok(Self::Reset),
+            _ => Err(InvalidModeError(v)),
+        }
+    }
+}
+
 bitfield! {
     /// Status of the sensor.
+    #[derive(Clone, Copy, Default)]
     pub struct Status(u8);
     impl Debug;
     pub bool, running_normally, _: 7;
@@ -50,15 +126,106 @@ bitfield! {
     pub bool, new_data_in_gpr, _: 0;
 }
 
+impl Status {
+    /// Returns `true` if the sensor runs normally and new valid data is ready.
+    pub fn is_data_fresh(&self) -> bool {
+        self.running_normally() && self.data_is_ready() && self.validity_flag().is_stable()
+    }
+
+    /// Returns `true` if the sensor runs normally without error and its output is stable.
+    pub fn is_fully_operational(&self) -> bool {
+        self.running_normally() && !self.error() && self.validity_flag().is_stable()
+    }
+
+    /// Returns `true` if the sensor is [fully operational](Self::is_fully_operational) and new
+    /// data is ready.
+    pub fn measurement_is_fresh(&self) -> bool {
+        self.is_fully_operational() && self.data_is_ready()
+    }
+
+    /// Returns `true` if the sensor reported an error.
+    pub fn has_error(&self) -> bool {
+        self.error()
+    }
+
+    /// Returns `true` if the sensor is still in the warm-up or initial start-up phase.
+    pub fn is_warming_up(&self) -> bool {
+        self.validity_flag().is_warming_up()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Status {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "Status({=u8:#010b})", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Status {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Status {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Status)
+    }
+}
+
 // #[derive(BitfieldSpecifier)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Validity {
+    #[default]
     NormalOperation,
     WarmupPhase,
     InitStartupPhase,
     InvalidOutput,
 }
 
+impl Validity {
+    /// Returns `true` if the sensor operates normally and its output is stable.
+    pub fn is_stable(self) -> bool {
+        self == Self::NormalOperation
+    }
+
+    /// Returns `true` during the warm-up or initial start-up phase.
+    pub fn is_warming_up(self) -> bool {
+        matches!(self, Self::WarmupPhase | Self::InitStartupPhase)
+    }
+
+    /// Returns `true` if the sensor signals an invalid output.
+    pub fn is_error(self) -> bool {
+        self == Self::InvalidOutput
+    }
+
+    /// Returns a human-readable description, e.g. `"Warm-up phase"`.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::NormalOperation => "Normal operation",
+            Self::WarmupPhase => "Warm-up phase",
+            Self::InitStartupPhase => "Initial start-up phase",
+            Self::InvalidOutput => "Invalid output",
+        }
+    }
+}
+
+impl fmt::Display for Validity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+impl From<Validity> for &'static str {
+    fn from(v: Validity) -> Self {
+        v.description()
+    }
+}
+
 impl From<u8> for Validity {
     fn from(v: u8) -> Self {
         match v {
@@ -72,19 +239,22 @@ impl From<u8> for Validity {
 }
 
 bitfield! {
-    #[derive(Default)]
+    #[derive(Clone, Copy, Default)]
     struct InterruptRegister(u8);
     impl Debug;
-    from into InterruptState, _, set_interrupt_state: 6, 6;
-    from into PinMode,  ddfsdf, set_pin_mode: 5, 5;
-    bool,  _, set_on_data_in_gpr_register: 3;
-    bool,  _, set_on_data_in_data_register: 1;
-    bool,  _, set_enabled: 0;
+    from into InterruptState, interrupt_state, set_interrupt_state: 6, 6;
+    from into PinMode, pin_mode, set_pin_mode: 5, 5;
+    bool, on_data_in_gpr_register, set_on_data_in_gpr_register: 3;
+    bool, on_data_in_data_register, set_on_data_in_data_register: 1;
+    bool, enabled, set_enabled: 0;
 }
 
 // #[derive(BitfieldSpecifier)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Driver mode of the interrupt pin, open-drain after reset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum PinMode {
+    #[default]
     OpenDrain,
     PushPull,
 }
@@ -108,8 +278,11 @@ impl From<u8> for PinMode {
 }
 
 // #[derive(BitfieldSpecifier)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Polarity of the interrupt pin, active low after reset.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum InterruptState {
+    #[default]
     ActiveLow,
     ActiveHigh,
 }
@@ -132,10 +305,95 @@ impl From<u8> for InterruptState {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct InterruptConfig(InterruptRegister);
 
+#[cfg(feature = "defmt")]
+impl defmt::Format for InterruptConfig {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "InterruptConfig({=u8:#010b})", (self.0).0)
+    }
+}
+
 impl InterruptConfig {
+    /// Creates the configuration from the raw value of the `CONFIG` register.
+    pub fn from_raw(value: u8) -> Self {
+        Self(InterruptRegister(value))
+    }
+
+    /// Returns the raw value of the `CONFIG` register.
+    pub fn to_raw(self) -> u8 {
+        (self.0).0
+    }
+
+    /// Returns `true` if the interrupt pin is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.0.enabled()
+    }
+
+    /// Returns `true` if the interrupt fires on new data in the data registers.
+    pub fn fires_on_data_ready(&self) -> bool {
+        self.0.on_data_in_data_register()
+    }
+
+    /// Returns `true` if the interrupt fires on new data in the general purpose read registers.
+    pub fn fires_on_gpr_ready(&self) -> bool {
+        self.0.on_data_in_gpr_register()
+    }
+
+    /// Returns the driver mode of the interrupt pin.
+    pub fn pin_mode(&self) -> PinMode {
+        self.0.pin_mode()
+    }
+
+    /// Returns the polarity of the interrupt pin.
+    pub fn interrupt_state(&self) -> InterruptState {
+        self.0.interrupt_state()
+    }
+
+    /// Interrupt on new data in the data registers, push-pull and active high.
+    pub fn data_ready_push_pull_active_high() -> Self {
+        Self::default()
+            .enable_for_measure_data_is_ready()
+            .set_pin_mode(PinMode::PushPull)
+            .set_pin_interrupt_state(InterruptState::ActiveHigh)
+    }
+
+    /// Interrupt on new data in the data registers, open-drain and active low.
+    pub fn data_ready_open_drain_active_low() -> Self {
+        Self::default()
+            .enable_for_measure_data_is_ready()
+            .set_pin_mode(PinMode::OpenDrain)
+            .set_pin_interrupt_state(InterruptState::ActiveLow)
+    }
+
+    /// Interrupt on new data in the general purpose read registers, push-pull and active high.
+    pub fn gpr_ready_push_pull_active_high() -> Self {
+        Self::default()
+            .enable_for_data_in_read_register()
+            .set_pin_mode(PinMode::PushPull)
+            .set_pin_interrupt_state(InterruptState::ActiveHigh)
+    }
+
+    /// Interrupt pin disabled.
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Disables the interrupt pin and all interrupt sources, keeping the pin configuration.
+    pub fn disable_all(mut self) -> Self {
+        self.0.set_enabled(false);
+        self.0.set_on_data_in_data_register(false);
+        self.0.set_on_data_in_gpr_register(false);
+        self
+    }
+
+    /// Enables the interrupt for new data in both the data and the general purpose read registers.
+    pub fn enable_both(self) -> Self {
+        self.enable_for_measure_data_is_ready()
+            .enable_for_data_in_read_register()
+    }
+
     pub fn set_pin_interrupt_state(mut self, state: InterruptState) -> Self {
         self.0.set_interrupt_state(state);
         self
@@ -163,9 +421,12 @@ impl InterruptConfig {
     }
 }
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
 #[repr(u8)]
 pub enum AirQualityIndex {
+    #[default]
     Excellent = 1,
     Good = 2,
     Moderate = 3,
@@ -173,6 +434,285 @@ pub enum AirQualityIndex {
     Unhealthy = 5,
 }
 
+/// Serializes the level as its label, e.g. `"Excellent"`, in human-readable formats and as its
+/// index otherwise.
+#[cfg(feature = "serde")]
+impl serde::Serialize for AirQualityIndex {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(self.label())
+        } else {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+}
+
+/// Deserializes the level from its label or its index.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AirQualityIndex {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AirQualityIndexVisitor;
+
+        impl serde::de::Visitor<'_> for AirQualityIndexVisitor {
+            type Value = AirQualityIndex;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an air quality index from 1 to 5 or its label")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                u8::try_from(v)
+                    .ok()
+                    .and_then(AirQualityIndex::from_index)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                u8::try_from(v)
+                    .ok()
+                    .and_then(AirQualityIndex::from_index)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                AirQualityIndex::iter()
+                    .find(|aqi| aqi.label() == v)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(AirQualityIndexVisitor)
+        } else {
+            deserializer.deserialize_u8(AirQualityIndexVisitor)
+        }
+    }
+}
+
+impl AirQualityIndex {
+    /// The best possible air quality.
+    pub const BEST: AirQualityIndex = AirQualityIndex::Excellent;
+    /// The worst possible air quality.
+    pub const WORST: AirQualityIndex = AirQualityIndex::Unhealthy;
+    /// All levels from best to worst.
+    pub const ALL: [AirQualityIndex; 5] = [
+        AirQualityIndex::Excellent,
+        AirQualityIndex::Good,
+        AirQualityIndex::Moderate,
+        AirQualityIndex::Poor,
+        AirQualityIndex::Unhealthy,
+    ];
+
+    /// Returns an iterator over all levels from best to worst.
+    pub fn iter() -> impl Iterator<Item = AirQualityIndex> {
+        Self::ALL.into_iter()
+    }
+
+    /// Returns the number of levels.
+    pub const fn count() -> usize {
+        Self::ALL.len()
+    }
+
+    /// Returns `true` if the air quality is worse than `other`.
+    pub fn is_worse_than(self, other: AirQualityIndex) -> bool {
+        self > other
+    }
+
+    /// Returns `true` if the air quality is better than `other`.
+    pub fn is_better_than(self, other: AirQualityIndex) -> bool {
+        self < other
+    }
+
+    /// Returns the worse of two levels, e.g. to combine the readings of several sensors.
+    pub fn worst_of(a: AirQualityIndex, b: AirQualityIndex) -> AirQualityIndex {
+        a.max(b)
+    }
+
+    /// Returns the better of two levels.
+    pub fn best_of(a: AirQualityIndex, b: AirQualityIndex) -> AirQualityIndex {
+        a.min(b)
+    }
+
+    /// Returns the worst level in `levels`, `None` if it is empty.
+    pub fn worst_in(levels: &[AirQualityIndex]) -> Option<AirQualityIndex> {
+        levels.iter().copied().max()
+    }
+
+    /// Returns the best level in `levels`, `None` if it is empty.
+    pub fn best_in(levels: &[AirQualityIndex]) -> Option<AirQualityIndex> {
+        levels.iter().copied().min()
+    }
+
+    /// Returns the number of levels above [`AirQualityIndex::Excellent`], from 0 to 4.
+    pub fn steps_from_excellent(self) -> u8 {
+        self.as_u8() - Self::BEST.as_u8()
+    }
+
+    /// Returns a color for the level, from green for excellent to red for unhealthy.
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        match self {
+            Self::Excellent => (0, 255, 0),
+            Self::Good => (128, 255, 0),
+            Self::Moderate => (255, 255, 0),
+            Self::Poor => (255, 128, 0),
+            Self::Unhealthy => (255, 0, 0),
+        }
+    }
+
+    /// Returns a gray level for monochrome displays, from 255 for excellent to 0 for unhealthy.
+    pub fn to_grayscale(self) -> u8 {
+        let steps = u16::from(self.steps_from_excellent());
+        // At most 4 * 255 / 4 = 255, so the result fits into a `u8`.
+        255 - (steps * 255 / 4) as u8
+    }
+
+    /// Returns the name of the level, e.g. `"Excellent"`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Excellent => "Excellent",
+            Self::Good => "Good",
+            Self::Moderate => "Moderate",
+            Self::Poor => "Poor",
+            Self::Unhealthy => "Unhealthy",
+        }
+    }
+
+    /// Returns the recommendation of the UBA for the level.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Excellent => "Pure, fresh air - no measures needed.",
+            Self::Good => "No relevant objections - sufficient ventilation recommended.",
+            Self::Moderate => "Some objections - increased ventilation recommended, search for sources.",
+            Self::Poor => "Major objections - intensified ventilation recommended, search for sources.",
+            Self::Unhealthy => {
+                "Situation not acceptable - use only if unavoidable, intense ventilation recommended."
+            }
+        }
+    }
+
+    /// Converts a raw index into an [`AirQualityIndex`], rejecting values outside of 1 to 5.
+    pub fn try_from_u8(index: u8) -> Result<Self, InvalidAqiError> {
+        match index {
+            1 => Ok(Self::Excellent),
+            2 => Ok(Self::Good),
+            3 => Ok(Self::Moderate),
+            4 => Ok(Self::Poor),
+            5 => Ok(Self::Unhealthy),
+            _ => Err(InvalidAqiError(index)),
+        }
+    }
+
+    /// Converts a raw index into an [`AirQualityIndex`], returning `None` for values outside of 1 to 5.
+    pub fn from_index(index: u8) -> Option<Self> {
+        Self::try_from_u8(index).ok()
+    }
+
+    /// Returns the index from 1 (excellent) to 5 (unhealthy).
+    pub fn as_u8(self) -> u8 {
+        self as u8
+    }
+
+    /// Classifies a TVOC concentration according to the UBA, saturating at
+    /// [`AirQualityIndex::Unhealthy`] for values above [`Tvoc::MAX`].
+    pub fn from_tvoc(tvoc: Tvoc) -> Self {
+        Self::try_from(tvoc).unwrap_or(Self::Unhealthy)
+    }
+
+    /// Maps the UBA level onto the category of the US EPA AQI with the same rank.
+    ///
+    /// | UBA       | EPA                               |
+    /// |-----------|-----------------------------------|
+    /// | Excellent | Good                              |
+    /// | Good      | Moderate                          |
+    /// | Moderate  | Unhealthy for Sensitive Groups    |
+    /// | Poor      | Unhealthy                         |
+    /// | Unhealthy | Very Unhealthy                    |
+    ///
+    /// The scales are based on different pollutants, so this is an approximation for display
+    /// purposes. [`EpaAqiCategory::Hazardous`] is never returned.
+    pub fn to_epa_category(self) -> EpaAqiCategory {
+        match self {
+            Self::Excellent => EpaAqiCategory::Good,
+            Self::Good => EpaAqiCategory::Moderate,
+            Self::Moderate => EpaAqiCategory::UnhealthyForSensitiveGroups,
+            Self::Poor => EpaAqiCategory::Unhealthy,
+            Self::Unhealthy => EpaAqiCategory::VeryUnhealthy,
+        }
+    }
+
+    /// Returns the worse of the levels of an eCO2 and a TVOC concentration.
+    ///
+    /// eCO2 is classified by [`ECo2::to_aqi_saturating()`], TVOC by [`Tvoc::uba_category()`].
+    /// The result is calculated in software and may differ from the index reported by the sensor.
+    pub fn from_both(eco2: ECo2, tvoc: Tvoc) -> Self {
+        Self::worst_of(eco2.to_aqi_saturating(), tvoc.uba_category().into())
+    }
+}
+
+/// Air quality category of the US EPA AQI.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EpaAqiCategory {
+    /// AQI 0 to 50.
+    Good,
+    /// AQI 51 to 100.
+    Moderate,
+    /// AQI 101 to 150.
+    UnhealthyForSensitiveGroups,
+    /// AQI 151 to 200.
+    Unhealthy,
+    /// AQI 201 to 300.
+    VeryUnhealthy,
+    /// AQI 301 and above.
+    Hazardous,
+}
+
+impl EpaAqiCategory {
+    /// Returns the name of the category as used by the EPA, e.g. `"Unhealthy for Sensitive Groups"`.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Good => "Good",
+            Self::Moderate => "Moderate",
+            Self::UnhealthyForSensitiveGroups => "Unhealthy for Sensitive Groups",
+            Self::Unhealthy => "Unhealthy",
+            Self::VeryUnhealthy => "Very Unhealthy",
+            Self::Hazardous => "Hazardous",
+        }
+    }
+}
+
+/// Returns the worse of two levels, see [`AirQualityIndex::worst_of`].
+pub fn worst_of(a: AirQualityIndex, b: AirQualityIndex) -> AirQualityIndex {
+    AirQualityIndex::worst_of(a, b)
+}
+
+/// Returns the better of two levels, see [`AirQualityIndex::best_of`].
+pub fn best_of(a: AirQualityIndex, b: AirQualityIndex) -> AirQualityIndex {
+    AirQualityIndex::best_of(a, b)
+}
+
+impl fmt::Display for AirQualityIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.label(), *self as u8)
+    }
+}
+
+/// Writes the numeric index.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for AirQualityIndex {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "{}", self.as_u8())
+    }
+}
+
+/// Converts a raw index into an [`AirQualityIndex`].
+///
+/// Values outside of 1 to 5 silently saturate to [`AirQualityIndex::Unhealthy`].
+/// Use [`AirQualityIndex::try_from_u8()`] to reject them instead.
 impl From<u8> for AirQualityIndex {
     fn from(i: u8) -> Self {
         match i {
@@ -186,71 +726,1500 @@ impl From<u8> for AirQualityIndex {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
-pub struct ECo2(u16);
+impl From<AirQualityIndex> for u8 {
+    fn from(aqi: AirQualityIndex) -> Self {
+        aqi as u8
+    }
+}
 
-impl From<u16> for ECo2 {
-    fn from(v: u16) -> Self {
-        Self(v)
+impl From<AirQualityIndex> for u16 {
+    fn from(aqi: AirQualityIndex) -> Self {
+        aqi.as_u8().into()
     }
 }
 
-impl TryFrom<ECo2> for AirQualityIndex {
-    type Error = AirqualityConvError;
+impl From<AirQualityIndex> for u32 {
+    fn from(aqi: AirQualityIndex) -> Self {
+        aqi.as_u8().into()
+    }
+}
 
-    fn try_from(e: ECo2) -> Result<Self, Self::Error> {
-        let value = e.0;
-        match value {
-            400..=599 => Ok(Self::Excellent),
-            600..=799 => Ok(Self::Good),
-            800..=999 => Ok(Self::Moderate),
-            1000..=1499 => Ok(Self::Poor),
-            1500..=u16::MAX => Ok(Self::Unhealthy),
-            _ => Err(AirqualityConvError(value)),
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct ECo2(u16);
+
+/// Returns [`ECo2::OUTDOOR_BASELINE`], the lowest concentration within the valid range.
+impl Default for ECo2 {
+    fn default() -> Self {
+        Self::OUTDOOR_BASELINE
     }
 }
 
-impl Deref for ECo2 {
-    type Target = u16;
+impl ECo2 {
+    /// Lowest eCO2 concentration in ppm the sensor reports in normal operation.
+    pub const MIN: u16 = 400;
+    /// Highest eCO2 concentration in ppm the sensor reports.
+    pub const MAX: u16 = 65000;
+    /// Lowest eCO2 concentration the sensor reports in normal operation.
+    pub const MIN_VALID: ECo2 = ECo2(Self::MIN);
+    /// Highest eCO2 concentration the sensor reports.
+    pub const MAX_VALID: ECo2 = ECo2(Self::MAX);
+    /// The sensor's 400 ppm floor, which it reports for clean air and uses as its reference
+    /// point, unlike the actual outdoor concentration [`ECo2::OUTDOOR_AIR`].
+    pub const OUTDOOR_BASELINE: ECo2 = ECo2(Self::MIN);
+    /// Current average CO2 concentration of outdoor air, the reference of
+    /// [`ECo2::above_outdoor_baseline()`] and friends.
+    pub const OUTDOOR_AIR: ECo2 = ECo2(420);
 
-    fn deref(&self) -> &Self::Target {
+    /// Creates an eCO2 value from a concentration in ppm, usable in `const` contexts unlike
+    /// [`From<u16>`].
+    pub const fn from_ppm(ppm: u16) -> Self {
+        Self(ppm)
+    }
+
+    /// Returns the eCO2 concentration in ppm.
+    pub fn ppm(self) -> u16 {
+        self.0
+    }
+
+    /// Borrows the eCO2 concentration in ppm.
+    pub fn ppm_ref(&self) -> &u16 {
         &self.0
     }
+
+    /// Returns `true` if the value is within the output range of the sensor.
+    ///
+    /// Values below 400 ppm are reported during start-up or indicate an error.
+    pub fn is_in_valid_range(self) -> bool {
+        (Self::MIN_VALID..=Self::MAX_VALID).contains(&self)
+    }
+
+    /// Classifies the concentration, treating values below 400 ppm as
+    /// [`AirQualityIndex::Excellent`] instead of failing like the [`TryFrom`] conversion.
+    #[doc(alias = "to_aqi_clamped")]
+    pub fn to_aqi_saturating(self) -> AirQualityIndex {
+        AirQualityIndex::try_from(self).unwrap_or(AirQualityIndex::Excellent)
+    }
+
+    /// Clamps the value to the output range of the sensor.
+    pub fn clamp_to_valid(self) -> Self {
+        self.clamp(Self::MIN_VALID, Self::MAX_VALID)
+    }
+
+    /// Returns `true` if the concentration is above [`ECo2::OUTDOOR_AIR`].
+    pub fn above_outdoor_baseline(self) -> bool {
+        self > Self::OUTDOOR_AIR
+    }
+
+    /// Returns the ppm above [`ECo2::OUTDOOR_AIR`], 0 if the concentration is below it.
+    pub fn ppm_above_outdoor(self) -> u16 {
+        self.0.saturating_sub(Self::OUTDOOR_AIR.0)
+    }
+
+    /// Returns how many percent the concentration is above [`ECo2::OUTDOOR_AIR`], 0 if it is
+    /// below it.
+    pub fn percent_above_outdoor(self) -> u32 {
+        u32::from(self.ppm_above_outdoor()) * 100 / u32::from(Self::OUTDOOR_AIR.0)
+    }
+
+    /// Adds `ppm`, saturating at `u16::MAX`.
+    pub fn saturating_add(self, ppm: u16) -> Self {
+        Self(self.0.saturating_add(ppm))
+    }
+
+    /// Subtracts `ppm`, saturating at 0.
+    pub fn saturating_sub(self, ppm: u16) -> Self {
+        Self(self.0.saturating_sub(ppm))
+    }
 }
 
-impl DerefMut for ECo2 {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+/// Adds a concentration in ppm, saturating at `u16::MAX`.
+impl Add<u16> for ECo2 {
+    type Output = Self;
+
+    fn add(self, rhs: u16) -> Self::Output {
+        self.saturating_add(rhs)
     }
 }
 
-#[cfg(test)]
-mod test {
+/// Returns the signed difference of two concentrations.
+impl Sub for ECo2 {
+    type Output = ECo2Delta;
 
-    use crate::{InterruptConfig, PinMode, Status, Validity};
+    fn sub(self, rhs: Self) -> Self::Output {
+        ECo2Delta(i32::from(self.0) - i32::from(rhs.0))
+    }
+}
 
-    #[test]
-    fn test_status_register_layout() {
-        let status = Status(0b00000001);
-        assert!(status.new_data_in_gpr());
+/// Signed difference of two eCO2 readings in ppm.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct ECo2Delta(pub i32);
 
-        let status = Status(0b10000100);
-        assert!(status.running_normally());
-        assert!(matches!(status.validity_flag(), Validity::WarmupPhase));
+impl ECo2Delta {
+    /// Returns `true` if the concentration increased.
+    pub fn is_increasing(self) -> bool {
+        self.0 > 0
+    }
 
-        let status = Status(0b00001110);
-        assert!(status.data_is_ready());
-        assert!(matches!(status.validity_flag(), Validity::InvalidOutput))
+    /// Returns the magnitude of the difference in ppm.
+    pub fn abs(self) -> u16 {
+        u16::try_from(self.0.unsigned_abs()).unwrap_or(u16::MAX)
     }
 
-    #[test]
-    fn test_interrupt_config() {
-        let config = InterruptConfig::default()
-            .enable_for_measure_data_is_ready()
-            .set_pin_mode(PinMode::PushPull)
-            .finish();
-        assert_eq!(config.0, 0b00100011)
+    /// Returns 1 for an increase, -1 for a decrease and 0 otherwise.
+    pub fn signum(self) -> i32 {
+        self.0.signum()
+    }
+}
+
+impl fmt::Display for ECo2Delta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+} ppm", self.0)
+    }
+}
+
+/// Scales the concentration, saturating at `u16::MAX`.
+impl Mul<u16> for ECo2 {
+    type Output = Self;
+
+    fn mul(self, rhs: u16) -> Self::Output {
+        Self(self.0.saturating_mul(rhs))
+    }
+}
+
+/// Divides the concentration, e.g. to average a sum of readings.
+///
+/// # Panics
+///
+/// Panics if `rhs` is 0.
+impl Div<u16> for ECo2 {
+    type Output = Self;
+
+    fn div(self, rhs: u16) -> Self::Output {
+        Self(self.0 / rhs)
+    }
+}
+
+/// Adds up concentrations, saturating at `u16::MAX`. The sum of no readings is 0 ppm.
+impl Sum for ECo2 {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(saturating_sum(iter.map(|e| e.0)))
+    }
+}
+
+impl<'a> Sum<&'a ECo2> for ECo2 {
+    fn sum<I: Iterator<Item = &'a ECo2>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Returns the mean of `count` `u16` values adding up to `sum`, `None` if `count` is 0.
+pub(crate) fn mean_u16(sum: u64, count: u64) -> Option<u16> {
+    // The mean of `u16` values always fits into a `u16`.
+    sum.checked_div(count).map(|mean| mean as u16)
+}
+
+/// Sums the values in a `u32`, clamping the result to `u16::MAX`.
+fn saturating_sum(iter: impl Iterator<Item = u16>) -> u16 {
+    let sum = iter.fold(0u32, |sum, v| sum.saturating_add(u32::from(v)));
+    u16::try_from(sum).unwrap_or(u16::MAX)
+}
+
+impl fmt::Display for ECo2 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ppm eCO₂", self.0)
+    }
+}
+
+/// Writes the concentration in ppm.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for ECo2 {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "{}", self.0)
+    }
+}
+
+impl From<u16> for ECo2 {
+    fn from(v: u16) -> Self {
+        Self::from_ppm(v)
+    }
+}
+
+impl From<ECo2> for u16 {
+    fn from(e: ECo2) -> Self {
+        e.0
+    }
+}
+
+impl From<ECo2> for u32 {
+    fn from(e: ECo2) -> Self {
+        e.0.into()
+    }
+}
+
+impl From<ECo2> for i32 {
+    fn from(e: ECo2) -> Self {
+        e.0.into()
+    }
+}
+
+impl TryFrom<ECo2> for AirQualityIndex {
+    type Error = AirqualityConvError;
+
+    fn try_from(e: ECo2) -> Result<Self, Self::Error> {
+        let value = e.0;
+        match value {
+            ECo2::MIN..=599 => Ok(Self::Excellent),
+            600..=799 => Ok(Self::Good),
+            800..=999 => Ok(Self::Moderate),
+            1000..=1499 => Ok(Self::Poor),
+            1500..=u16::MAX => Ok(Self::Unhealthy),
+            _ => Err(AirqualityConvError(value)),
+        }
+    }
+}
+
+impl Deref for ECo2 {
+    type Target = u16;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Firmware version of the sensor.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FirmwareVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub patch: u8,
+}
+
+impl FirmwareVersion {
+    /// Returns `true` if this version is the given version or newer.
+    pub fn is_at_least(&self, major: u8, minor: u8, patch: u8) -> bool {
+        *self
+            >= Self {
+                major,
+                minor,
+                patch,
+            }
+    }
+
+    /// Formats the version as `"major.minor.patch"` without allocating.
+    #[cfg(feature = "heapless")]
+    pub fn to_heapless_string(&self) -> heapless::String<12> {
+        use core::fmt::Write;
+
+        let mut s = heapless::String::new();
+        // "255.255.255" is the longest possible version and fits into the capacity.
+        let _ = write!(s, "{}", self);
+        s
+    }
+}
+
+impl From<(u8, u8, u8)> for FirmwareVersion {
+    fn from((major, minor, patch): (u8, u8, u8)) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for FirmwareVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Total Volatile Organic Compounds (TVOC) concentration in ppb.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[cfg_attr(feature = "ufmt", derive(ufmt::derive::uDebug))]
+pub struct Tvoc(u16);
+
+impl Tvoc {
+    /// Lowest TVOC concentration the sensor reports in ppb.
+    pub const MIN: u16 = 0;
+    /// Highest TVOC concentration the sensor reports in ppb.
+    pub const MAX: u16 = 65000;
+    /// TVOC concentration of clean air.
+    pub const CLEAN_AIR: Tvoc = Tvoc(Self::MIN);
+
+    /// Creates a TVOC value from a concentration in ppb, usable in `const` contexts unlike
+    /// [`From<u16>`].
+    pub const fn from_ppb(ppb: u16) -> Self {
+        Self(ppb)
+    }
+
+    /// Returns the TVOC concentration in ppb.
+    pub fn ppb(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` below 220 ppb, the UBA levels excellent and good.
+    pub fn is_low_pollution(self) -> bool {
+        self.0 < 220
+    }
+
+    /// Returns `true` from 220 ppb to below 660 ppb, the UBA level moderate.
+    pub fn is_moderate_pollution(self) -> bool {
+        (220..660).contains(&self.0)
+    }
+
+    /// Returns `true` from 660 ppb, the UBA levels poor and unhealthy.
+    pub fn is_high_pollution(self) -> bool {
+        self.0 >= 660
+    }
+
+    /// Returns `true` from 220 ppb, where the UBA recommends increased ventilation.
+    pub fn needs_ventilation(self) -> bool {
+        !self.is_low_pollution()
+    }
+
+    /// Classifies the concentration according to the TVOC guidelines of the UBA.
+    pub fn uba_category(self) -> TvocCategory {
+        match self.0 {
+            0..=64 => TvocCategory::Excellent,
+            65..=219 => TvocCategory::Good,
+            220..=659 => TvocCategory::Moderate,
+            660..=2199 => TvocCategory::Poor,
+            _ => TvocCategory::Unhealthy,
+        }
+    }
+}
+
+/// TVOC level according to the guidelines of the UBA.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TvocCategory {
+    /// Below 65 ppb.
+    Excellent,
+    /// 65 to 219 ppb.
+    Good,
+    /// 220 to 659 ppb.
+    Moderate,
+    /// 660 to 2199 ppb.
+    Poor,
+    /// 2200 ppb and above.
+    Unhealthy,
+}
+
+impl TvocCategory {
+    /// Returns the hygienic assessment of the UBA for the level.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Excellent => "No hygienic concerns",
+            Self::Good => "No relevant hygienic concerns",
+            Self::Moderate => "Some hygienic concerns",
+            Self::Poor => "Major hygienic concerns",
+            Self::Unhealthy => "Situation not acceptable",
+        }
+    }
+}
+
+impl From<TvocCategory> for AirQualityIndex {
+    fn from(category: TvocCategory) -> Self {
+        match category {
+            TvocCategory::Excellent => Self::Excellent,
+            TvocCategory::Good => Self::Good,
+            TvocCategory::Moderate => Self::Moderate,
+            TvocCategory::Poor => Self::Poor,
+            TvocCategory::Unhealthy => Self::Unhealthy,
+        }
+    }
+}
+
+impl fmt::Display for Tvoc {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ppb TVOC", self.0)
+    }
+}
+
+/// Writes the concentration in ppb.
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Tvoc {
+    fn fmt<W: ufmt::uWrite + ?Sized>(
+        &self,
+        f: &mut ufmt::Formatter<'_, W>,
+    ) -> Result<(), W::Error> {
+        ufmt::uwrite!(f, "{}", self.0)
+    }
+}
+
+impl From<u16> for Tvoc {
+    fn from(v: u16) -> Self {
+        Self::from_ppb(v)
+    }
+}
+
+impl From<Tvoc> for u16 {
+    fn from(t: Tvoc) -> Self {
+        t.0
+    }
+}
+
+/// Returns the signed difference of two concentrations.
+impl Sub for Tvoc {
+    type Output = TvocDelta;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        TvocDelta(i32::from(self.0) - i32::from(rhs.0))
+    }
+}
+
+/// Adds up concentrations, saturating at `u16::MAX`. The sum of no readings is 0 ppb.
+impl Sum for Tvoc {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(saturating_sum(iter.map(|t| t.0)))
+    }
+}
+
+impl<'a> Sum<&'a Tvoc> for Tvoc {
+    fn sum<I: Iterator<Item = &'a Tvoc>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Signed difference of two TVOC readings in ppb.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct TvocDelta(pub i32);
+
+impl TvocDelta {
+    /// Returns `true` if the concentration increased.
+    pub fn is_increasing(self) -> bool {
+        self.0 > 0
+    }
+
+    /// Returns the magnitude of the difference in ppb.
+    pub fn abs(self) -> u16 {
+        u16::try_from(self.0.unsigned_abs()).unwrap_or(u16::MAX)
+    }
+
+    /// Returns 1 for an increase, -1 for a decrease and 0 otherwise.
+    pub fn signum(self) -> i32 {
+        self.0.signum()
+    }
+}
+
+impl fmt::Display for TvocDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+} ppb", self.0)
+    }
+}
+
+impl TryFrom<Tvoc> for AirQualityIndex {
+    type Error = TvocConvError;
+
+    /// Classifies a TVOC concentration with [`Tvoc::uba_category()`], rejecting values above
+    /// [`Tvoc::MAX`].
+    fn try_from(t: Tvoc) -> Result<Self, Self::Error> {
+        if (Tvoc::MIN..=Tvoc::MAX).contains(&t.0) {
+            Ok(Self::from(t.uba_category()))
+        } else {
+            Err(TvocConvError(t.0))
+        }
+    }
+}
+
+impl Deref for Tvoc {
+    type Target = u16;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A measured value together with the validity of the sensor output when it was taken.
+///
+/// Dereferences to the value, e.g. `reading.ppm()` for a `SensorReading<ECo2>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorReading<T> {
+    pub value: T,
+    pub validity: Validity,
+    /// Reserved for the age of the data, always 0 for now.
+    pub data_age: u8,
+}
+
+impl<T> SensorReading<T> {
+    /// Wraps a value taken with the given validity.
+    pub const fn new(value: T, validity: Validity) -> Self {
+        Self {
+            value,
+            validity,
+            data_age: 0,
+        }
+    }
+
+    /// Returns `true` if the sensor was in normal operation when the value was taken.
+    pub fn is_valid(&self) -> bool {
+        self.validity.is_stable()
+    }
+}
+
+impl<T> Deref for SensorReading<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+/// Snapshot of all measurement registers read in a single transaction.
+///
+/// The default holds fresh-air values, [`AirQualityIndex::Excellent`], 0 ppb TVOC and 400 ppm
+/// eCO2, with an empty status.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeasurementBundle {
+    pub status: Status,
+    pub aqi: SensorReading<AirQualityIndex>,
+    pub tvoc: SensorReading<Tvoc>,
+    pub eco2: SensorReading<ECo2>,
+}
+
+impl MeasurementBundle {
+    /// Returns `true` if the sensor was in normal operation when the measurement was taken.
+    pub fn is_valid(&self) -> bool {
+        self.status.validity_flag().is_stable()
+    }
+
+    /// Returns the air quality index reported by the sensor, which already combines all
+    /// measured gases.
+    pub fn worst_indicator(&self) -> AirQualityIndex {
+        self.aqi.value
+    }
+
+    /// Classifies the eCO2 concentration alone, see [`ECo2::to_aqi_saturating()`].
+    pub fn eco2_aqi(&self) -> AirQualityIndex {
+        self.eco2.to_aqi_saturating()
+    }
+
+    /// Classifies the TVOC concentration alone, see [`Tvoc::uba_category()`].
+    pub fn tvoc_aqi(&self) -> TvocCategory {
+        self.tvoc.uba_category()
+    }
+
+    /// Returns `true` if the eCO2 concentration rates at least as bad as the TVOC concentration.
+    pub fn is_eco2_dominant(&self) -> bool {
+        self.eco2_aqi() >= AirQualityIndex::from(self.tvoc_aqi())
+    }
+
+    /// Parses the registers `DATA_STATUS` through `DATA_ECO2`.
+    fn from_registers(buffer: [u8; 6]) -> Self {
+        let status = Status(buffer[0]);
+        let validity = status.validity_flag();
+        Self {
+            status,
+            aqi: SensorReading::new(AirQualityIndex::from(buffer[1] & 0x07), validity),
+            tvoc: SensorReading::new(
+                Tvoc::from(u16::from_le_bytes([buffer[2], buffer[3]])),
+                validity,
+            ),
+            eco2: SensorReading::new(
+                ECo2::from(u16::from_le_bytes([buffer[4], buffer[5]])),
+                validity,
+            ),
+        }
+    }
+}
+
+/// Builds a valid measurement with the given concentrations for tests.
+#[cfg(test)]
+pub(crate) fn bundle(eco2: u16, tvoc: u16) -> MeasurementBundle {
+    MeasurementBundle {
+        status: Status(0b10000010),
+        aqi: SensorReading::new(AirQualityIndex::Good, Validity::NormalOperation),
+        tvoc: SensorReading::new(Tvoc::from(tvoc), Validity::NormalOperation),
+        eco2: SensorReading::new(ECo2::from(eco2), Validity::NormalOperation),
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::{
+        bundle, Address, AirQualityIndex, ECo2, ECo2Delta, EpaAqiCategory, FirmwareVersion,
+        InterruptConfig, InterruptState, MeasurementBundle, OperationMode, PinMode, SensorReading,
+        Status, Tvoc, TvocCategory, TvocDelta, Validity,
+    };
+
+    #[test]
+    fn test_status_register_layout() {
+        let status = Status(0b00000001);
+        assert!(status.new_data_in_gpr());
+
+        let status = Status(0b10000100);
+        assert!(status.running_normally());
+        assert_eq!(status.validity_flag(), Validity::WarmupPhase);
+
+        let status = Status(0b00001110);
+        assert!(status.data_is_ready());
+        assert_eq!(status.validity_flag(), Validity::InvalidOutput);
+        assert!(!status.is_fully_operational());
+
+        let status = Status(0b10000010);
+        assert!(status.is_fully_operational());
+        assert!(status.measurement_is_fresh());
+
+        let status = Status(0b10000000);
+        assert!(status.is_fully_operational());
+        assert!(!status.measurement_is_fresh());
+
+        let status = Status(0b11000010);
+        assert!(!status.is_fully_operational());
+        assert!(!status.measurement_is_fresh());
+
+        let status = Status(0b10000110);
+        assert!(!status.is_fully_operational());
+
+        let status = Status(0b00000010);
+        assert!(!status.is_fully_operational());
+    }
+
+    #[test]
+    fn test_sensor_reading() {
+        let reading = SensorReading::new(ECo2::from(412), Validity::NormalOperation);
+        assert!(reading.is_valid());
+        assert_eq!(reading.ppm(), 412);
+        assert_eq!(reading.data_age, 0);
+
+        let warming_up = SensorReading::new(ECo2::from(412), Validity::WarmupPhase);
+        assert!(!warming_up.is_valid());
+        assert!(!SensorReading::new(Tvoc::from(0), Validity::InvalidOutput).is_valid());
+
+        let bundle = MeasurementBundle::from_registers([0b10000110, 2, 87, 0, 0x9C, 0x01]);
+        assert_eq!(bundle.eco2.validity, Validity::WarmupPhase);
+        assert!(!bundle.eco2.is_valid());
+        assert!(!bundle.tvoc.is_valid());
+        assert!(!bundle.aqi.is_valid());
+        assert_eq!(bundle.eco2.value, ECo2::from(412));
+    }
+
+    #[test]
+    fn test_measurement_bundle_dominant_pollutant() {
+        let mut eco2_high = bundle(1200, 100);
+        eco2_high.aqi.value = AirQualityIndex::Poor;
+        assert_eq!(eco2_high.worst_indicator(), AirQualityIndex::Poor);
+        assert_eq!(eco2_high.eco2_aqi(), AirQualityIndex::Poor);
+        assert_eq!(eco2_high.tvoc_aqi(), TvocCategory::Good);
+        assert!(eco2_high.is_eco2_dominant());
+
+        let tvoc_high = bundle(500, 1000);
+        assert_eq!(tvoc_high.eco2_aqi(), AirQualityIndex::Excellent);
+        assert_eq!(tvoc_high.tvoc_aqi(), TvocCategory::Poor);
+        assert!(!tvoc_high.is_eco2_dominant());
+
+        assert!(bundle(900, 300).is_eco2_dominant());
+    }
+
+    #[test]
+    fn test_operation_mode() {
+        let expected = [
+            (0x00, OperationMode::Sleep),
+            (0x01, OperationMode::Idle),
+            (0x02, OperationMode::Standard),
+            (0xF0, OperationMode::Reset),
+        ];
+        for (byte, mode) in expected {
+            assert_eq!(OperationMode::try_from(byte).unwrap(), mode);
+            assert_eq!(mode as u8, byte);
+        }
+        assert_eq!(OperationMode::try_from(0x03).unwrap_err().value(), 0x03);
+    }
+
+    #[test]
+    fn test_status_accessors() {
+        let status = Status(0b10000010);
+        assert!(status.is_data_fresh());
+        assert!(!status.has_error());
+        assert!(!status.is_warming_up());
+
+        let copy = status;
+        assert_eq!(copy.0, status.0);
+
+        let status = Status(0b00000010);
+        assert!(!status.is_data_fresh());
+
+        let status = Status(0b10000110);
+        assert!(!status.is_data_fresh());
+        assert!(status.is_warming_up());
+
+        let status = Status(0b10001000);
+        assert!(status.is_warming_up());
+
+        let status = Status(0b11001110);
+        assert!(status.has_error());
+        assert!(!status.is_warming_up());
+        assert!(!status.is_data_fresh());
+    }
+
+    #[test]
+    fn test_validity_helpers() {
+        let expected = [
+            (
+                Validity::NormalOperation,
+                true,
+                false,
+                false,
+                "Normal operation",
+            ),
+            (Validity::WarmupPhase, false, true, false, "Warm-up phase"),
+            (
+                Validity::InitStartupPhase,
+                false,
+                true,
+                false,
+                "Initial start-up phase",
+            ),
+            (
+                Validity::InvalidOutput,
+                false,
+                false,
+                true,
+                "Invalid output",
+            ),
+        ];
+        for (validity, stable, warming_up, error, description) in expected {
+            assert_eq!(validity.is_stable(), stable);
+            assert_eq!(validity.is_warming_up(), warming_up);
+            assert_eq!(validity.is_error(), error);
+            assert_eq!(validity.description(), description);
+            assert_eq!(validity.to_string(), description);
+            assert_eq!(<&'static str>::from(validity), description);
+        }
+    }
+
+    #[test]
+    fn test_interrupt_config() {
+        let config = InterruptConfig::default()
+            .enable_for_measure_data_is_ready()
+            .set_pin_mode(PinMode::PushPull)
+            .finish();
+        assert_eq!(config.0, 0b00100011)
+    }
+
+    #[test]
+    fn test_interrupt_config_raw_round_trip() {
+        let config = InterruptConfig::default()
+            .enable_for_data_in_read_register()
+            .set_pin_mode(PinMode::PushPull)
+            .set_pin_interrupt_state(InterruptState::ActiveHigh);
+        let raw = config.to_raw();
+        assert_eq!(raw, 0b01101001);
+        assert_eq!(InterruptConfig::from_raw(raw).to_raw(), raw);
+        assert_eq!(InterruptConfig::from_raw(raw).finish().0, config.finish().0);
+    }
+
+    #[test]
+    fn test_interrupt_config_default() {
+        assert_eq!(InterruptConfig::default().finish().0, 0b00000000);
+        let config = InterruptConfig::default()
+            .set_pin_mode(PinMode::default())
+            .set_pin_interrupt_state(InterruptState::default())
+            .finish();
+        assert_eq!(config.0, 0b00000000);
+    }
+
+    #[test]
+    fn test_interrupt_config_presets() {
+        let expected = [
+            (
+                InterruptConfig::data_ready_push_pull_active_high(),
+                0b01100011,
+            ),
+            (
+                InterruptConfig::data_ready_open_drain_active_low(),
+                0b00000011,
+            ),
+            (
+                InterruptConfig::gpr_ready_push_pull_active_high(),
+                0b01101001,
+            ),
+            (InterruptConfig::disabled(), 0b00000000),
+            (InterruptConfig::default().enable_both(), 0b00001011),
+            (
+                InterruptConfig::data_ready_push_pull_active_high().disable_all(),
+                0b01100000,
+            ),
+        ];
+        for (config, byte) in expected {
+            assert_eq!(config.finish().0, byte);
+        }
+    }
+
+    #[test]
+    fn test_interrupt_config_accessors() {
+        use crate::{InterruptState, PinMode};
+
+        let expected = [
+            (
+                InterruptConfig::data_ready_push_pull_active_high(),
+                (
+                    true,
+                    true,
+                    false,
+                    PinMode::PushPull,
+                    InterruptState::ActiveHigh,
+                ),
+            ),
+            (
+                InterruptConfig::data_ready_open_drain_active_low(),
+                (
+                    true,
+                    true,
+                    false,
+                    PinMode::OpenDrain,
+                    InterruptState::ActiveLow,
+                ),
+            ),
+            (
+                InterruptConfig::gpr_ready_push_pull_active_high(),
+                (
+                    true,
+                    false,
+                    true,
+                    PinMode::PushPull,
+                    InterruptState::ActiveHigh,
+                ),
+            ),
+            (
+                InterruptConfig::disabled(),
+                (
+                    false,
+                    false,
+                    false,
+                    PinMode::OpenDrain,
+                    InterruptState::ActiveLow,
+                ),
+            ),
+            (
+                InterruptConfig::default().enable_both(),
+                (
+                    true,
+                    true,
+                    true,
+                    PinMode::OpenDrain,
+                    InterruptState::ActiveLow,
+                ),
+            ),
+            (
+                InterruptConfig::data_ready_push_pull_active_high().disable_all(),
+                (
+                    false,
+                    false,
+                    false,
+                    PinMode::PushPull,
+                    InterruptState::ActiveHigh,
+                ),
+            ),
+        ];
+        for (config, accessors) in expected {
+            assert_eq!(
+                (
+                    config.is_enabled(),
+                    config.fires_on_data_ready(),
+                    config.fires_on_gpr_ready(),
+                    config.pin_mode(),
+                    config.interrupt_state(),
+                ),
+                accessors
+            );
+            let raw = InterruptConfig::from_raw(config.to_raw());
+            assert_eq!(raw.to_raw(), config.to_raw());
+            assert_eq!(raw.pin_mode(), accessors.3);
+        }
+    }
+
+    #[test]
+    fn test_airquality_index_display() {
+        let expected = [
+            (AirQualityIndex::Excellent, "Excellent", "Excellent (1)"),
+            (AirQualityIndex::Good, "Good", "Good (2)"),
+            (AirQualityIndex::Moderate, "Moderate", "Moderate (3)"),
+            (AirQualityIndex::Poor, "Poor", "Poor (4)"),
+            (AirQualityIndex::Unhealthy, "Unhealthy", "Unhealthy (5)"),
+        ];
+        for (aqi, label, display) in expected {
+            assert_eq!(aqi.label(), label);
+            assert_eq!(aqi.to_string(), display);
+            assert!(!aqi.description().is_empty());
+        }
+        assert_eq!(
+            AirQualityIndex::Excellent.description(),
+            "Pure, fresh air - no measures needed."
+        );
+        assert_eq!(
+            AirQualityIndex::Poor.description(),
+            "Major objections - intensified ventilation recommended, search for sources."
+        );
+    }
+
+    #[test]
+    fn test_airquality_index_try_from_u8() {
+        let expected = [
+            AirQualityIndex::Excellent,
+            AirQualityIndex::Good,
+            AirQualityIndex::Moderate,
+            AirQualityIndex::Poor,
+            AirQualityIndex::Unhealthy,
+        ];
+        for (index, aqi) in (1..=5).zip(expected) {
+            assert_eq!(AirQualityIndex::try_from_u8(index).unwrap(), aqi);
+        }
+        for index in [0, 6, 255] {
+            let error = AirQualityIndex::try_from_u8(index).unwrap_err();
+            assert_eq!(error.value(), index);
+            assert_eq!(AirQualityIndex::from(index), AirQualityIndex::Unhealthy);
+        }
+    }
+
+    #[test]
+    fn test_airquality_index_integer_round_trip() {
+        for index in 1..=5 {
+            let aqi = AirQualityIndex::from_index(index).unwrap();
+            assert_eq!(aqi.as_u8(), index);
+            assert_eq!(u8::from(aqi), index);
+            assert_eq!(u16::from(aqi), u16::from(index));
+            assert_eq!(u32::from(aqi), u32::from(index));
+            assert_eq!(AirQualityIndex::from(u8::from(aqi)), aqi);
+        }
+        assert_eq!(AirQualityIndex::from_index(0), None);
+        assert_eq!(AirQualityIndex::from_index(6), None);
+    }
+
+    #[test]
+    fn test_airquality_index_ordering() {
+        assert_eq!(AirQualityIndex::BEST.steps_from_excellent(), 0);
+        assert_eq!(AirQualityIndex::WORST.steps_from_excellent(), 4);
+        for a in 1..=5 {
+            let a = AirQualityIndex::from_index(a).unwrap();
+            assert_eq!(a.steps_from_excellent(), a.as_u8() - 1);
+            assert!(!a.is_better_than(AirQualityIndex::BEST));
+            assert!(!a.is_worse_than(AirQualityIndex::WORST));
+            for b in 1..=5 {
+                let b = AirQualityIndex::from_index(b).unwrap();
+                assert_eq!(a.is_worse_than(b), b.is_better_than(a));
+                assert_eq!(a.is_worse_than(b), a.as_u8() > b.as_u8());
+                assert_eq!(a.is_better_than(b), a.as_u8() < b.as_u8());
+            }
+        }
+    }
+
+    #[test]
+    fn test_airquality_index_worst_and_best() {
+        use AirQualityIndex::*;
+
+        assert_eq!(AirQualityIndex::worst_of(Good, Poor), Poor);
+        assert_eq!(AirQualityIndex::best_of(Good, Poor), Good);
+        assert_eq!(crate::worst_of(Moderate, Moderate), Moderate);
+        assert_eq!(crate::best_of(Unhealthy, Excellent), Excellent);
+
+        assert_eq!(AirQualityIndex::worst_in(&[]), None);
+        assert_eq!(AirQualityIndex::best_in(&[]), None);
+        assert_eq!(AirQualityIndex::worst_in(&[Moderate]), Some(Moderate));
+        assert_eq!(AirQualityIndex::best_in(&[Moderate]), Some(Moderate));
+        assert_eq!(AirQualityIndex::worst_in(&[Good, Good]), Some(Good));
+        assert_eq!(AirQualityIndex::best_in(&[Good, Good]), Some(Good));
+        let mixed = [Good, Unhealthy, Excellent, Moderate];
+        assert_eq!(AirQualityIndex::worst_in(&mixed), Some(Unhealthy));
+        assert_eq!(AirQualityIndex::best_in(&mixed), Some(Excellent));
+    }
+
+    #[test]
+    fn test_airquality_index_from_both() {
+        assert_eq!(
+            AirQualityIndex::from_both(ECo2::from(1600), Tvoc::from(30)),
+            AirQualityIndex::Unhealthy
+        );
+        assert_eq!(
+            AirQualityIndex::from_both(ECo2::from(450), Tvoc::from(700)),
+            AirQualityIndex::Poor
+        );
+        assert_eq!(
+            AirQualityIndex::from_both(ECo2::from(0), Tvoc::from(0)),
+            AirQualityIndex::Excellent
+        );
+        assert_eq!(
+            AirQualityIndex::from_both(ECo2::from(700), Tvoc::from(100)),
+            AirQualityIndex::Good
+        );
+    }
+
+    #[test]
+    fn test_airquality_index_epa_category() {
+        let expected = [
+            (AirQualityIndex::Excellent, EpaAqiCategory::Good, "Good"),
+            (AirQualityIndex::Good, EpaAqiCategory::Moderate, "Moderate"),
+            (
+                AirQualityIndex::Moderate,
+                EpaAqiCategory::UnhealthyForSensitiveGroups,
+                "Unhealthy for Sensitive Groups",
+            ),
+            (
+                AirQualityIndex::Poor,
+                EpaAqiCategory::Unhealthy,
+                "Unhealthy",
+            ),
+            (
+                AirQualityIndex::Unhealthy,
+                EpaAqiCategory::VeryUnhealthy,
+                "Very Unhealthy",
+            ),
+        ];
+        for (aqi, category, description) in expected {
+            assert_eq!(aqi.to_epa_category(), category);
+            assert_eq!(category.description(), description);
+        }
+        assert_eq!(EpaAqiCategory::Hazardous.description(), "Hazardous");
+    }
+
+    #[test]
+    fn test_airquality_index_colors() {
+        assert_eq!(AirQualityIndex::Excellent.to_rgb(), (0, 255, 0));
+        assert_eq!(AirQualityIndex::Good.to_rgb(), (128, 255, 0));
+        assert_eq!(AirQualityIndex::Moderate.to_rgb(), (255, 255, 0));
+        assert_eq!(AirQualityIndex::Poor.to_rgb(), (255, 128, 0));
+        assert_eq!(AirQualityIndex::Unhealthy.to_rgb(), (255, 0, 0));
+
+        let gray: Vec<_> = AirQualityIndex::iter()
+            .map(AirQualityIndex::to_grayscale)
+            .collect();
+        assert_eq!(gray.first(), Some(&255));
+        assert_eq!(gray.last(), Some(&0));
+        assert!(gray.windows(2).all(|w| w[0] > w[1]));
+    }
+
+    #[test]
+    fn test_airquality_index_iter() {
+        assert_eq!(AirQualityIndex::count(), 5);
+        assert_eq!(AirQualityIndex::iter().count(), AirQualityIndex::count());
+        assert!(AirQualityIndex::iter().eq(AirQualityIndex::ALL));
+        assert_eq!(AirQualityIndex::ALL[0], AirQualityIndex::BEST);
+        assert_eq!(AirQualityIndex::ALL[4], AirQualityIndex::WORST);
+        let levels: Vec<_> = AirQualityIndex::iter().collect();
+        assert!(levels.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_eco2_display() {
+        assert_eq!(ECo2::from(0).to_string(), "0 ppm eCO₂");
+        assert_eq!(ECo2::from(400).to_string(), "400 ppm eCO₂");
+        assert_eq!(ECo2::from(412).to_string(), "412 ppm eCO₂");
+        assert_eq!(ECo2::from(65000).to_string(), "65000 ppm eCO₂");
+        assert_eq!(ECo2::from(412).ppm(), 412);
+    }
+
+    #[test]
+    fn test_eco2_accessors() {
+        let eco2 = ECo2::from(412);
+        assert_eq!(eco2.ppm(), 412);
+        assert_eq!(*eco2.ppm_ref(), 412);
+        assert_eq!(*eco2, 412);
+    }
+
+    #[test]
+    fn test_integer_conversions() {
+        let eco2 = ECo2::from(ECo2::MAX);
+        assert_eq!(u16::from(eco2), 65000u16);
+        assert_eq!(u32::from(eco2), 65000u32);
+        assert_eq!(i32::from(eco2), 65000i32);
+        let tvoc = Tvoc::from(87);
+        assert_eq!(u16::from(tvoc), 87u16);
+        assert_eq!(tvoc.ppb(), 87);
+    }
+
+    #[test]
+    fn test_eco2_arithmetic() {
+        let eco2 = ECo2::from(600);
+        assert_eq!(eco2 + 50, ECo2::from(650));
+        assert_eq!(eco2 * 2, ECo2::from(1200));
+        assert_eq!(eco2 / 4, ECo2::from(150));
+        assert_eq!(ECo2::from(650) - eco2, ECo2Delta(50));
+        assert_eq!(eco2 - ECo2::from(650), ECo2Delta(-50));
+        assert_eq!(ECo2::from(0) - ECo2::from(u16::MAX), ECo2Delta(-65535));
+
+        assert_eq!(ECo2::from(u16::MAX - 1) + 10, ECo2::from(u16::MAX));
+        assert_eq!(ECo2::from(40000) * 2, ECo2::from(u16::MAX));
+        assert_eq!(eco2.saturating_add(u16::MAX), ECo2::from(u16::MAX));
+        assert_eq!(eco2.saturating_sub(700), ECo2::from(0));
+        assert_eq!(eco2.saturating_sub(200), ECo2::from(400));
+    }
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(core::iter::empty::<ECo2>().sum::<ECo2>(), ECo2::from(0));
+        assert_eq!(core::iter::empty::<Tvoc>().sum::<Tvoc>(), Tvoc::from(0));
+        assert_eq!([ECo2::from(420)].iter().sum::<ECo2>(), ECo2::from(420));
+        assert_eq!([Tvoc::from(42)].into_iter().sum::<Tvoc>(), Tvoc::from(42));
+
+        let eco2 = [ECo2::from(400), ECo2::from(500), ECo2::from(600)];
+        assert_eq!(eco2.iter().copied().sum::<ECo2>() / 3, ECo2::from(500));
+        let tvoc = [Tvoc::from(100), Tvoc::from(200)];
+        assert_eq!(tvoc.iter().sum::<Tvoc>(), Tvoc::from(300));
+
+        let eco2 = [ECo2::from(40000), ECo2::from(40000)];
+        assert_eq!(eco2.iter().sum::<ECo2>(), ECo2::from(u16::MAX));
+        let tvoc = [Tvoc::from(u16::MAX); 3];
+        assert_eq!(tvoc.into_iter().sum::<Tvoc>(), Tvoc::from(u16::MAX));
+    }
+
+    #[test]
+    fn test_range_constants() {
+        assert_eq!(ECo2::MIN_VALID.ppm(), ECo2::MIN);
+        assert_eq!(ECo2::MAX_VALID.ppm(), ECo2::MAX);
+        assert_eq!(
+            AirQualityIndex::try_from(ECo2::OUTDOOR_BASELINE).unwrap(),
+            AirQualityIndex::Excellent
+        );
+        assert!(AirQualityIndex::try_from(ECo2::from(ECo2::MIN - 1)).is_err());
+        assert_eq!(Tvoc::CLEAN_AIR.ppb(), Tvoc::MIN);
+        assert_eq!(
+            AirQualityIndex::try_from(Tvoc::CLEAN_AIR).unwrap(),
+            AirQualityIndex::Excellent
+        );
+    }
+
+    #[test]
+    fn test_eco2_to_aqi_saturating() {
+        assert!(AirQualityIndex::try_from(ECo2::from(0)).is_err());
+        assert_eq!(
+            ECo2::from(0).to_aqi_saturating(),
+            AirQualityIndex::Excellent
+        );
+        assert_eq!(
+            ECo2::from(399).to_aqi_saturating(),
+            AirQualityIndex::Excellent
+        );
+        assert_eq!(
+            ECo2::from(400).to_aqi_saturating(),
+            AirQualityIndex::Excellent
+        );
+        assert_eq!(
+            ECo2::from(900).to_aqi_saturating(),
+            AirQualityIndex::Moderate
+        );
+        assert_eq!(
+            ECo2::from(u16::MAX).to_aqi_saturating(),
+            AirQualityIndex::Unhealthy
+        );
+    }
+
+    #[test]
+    fn test_eco2_outdoor() {
+        let zero = ECo2::from(0);
+        assert!(!zero.above_outdoor_baseline());
+        assert_eq!(zero.ppm_above_outdoor(), 0);
+        assert_eq!(zero.percent_above_outdoor(), 0);
+
+        assert!(!ECo2::OUTDOOR_AIR.above_outdoor_baseline());
+        assert_eq!(ECo2::OUTDOOR_AIR.ppm_above_outdoor(), 0);
+        assert_eq!(ECo2::OUTDOOR_AIR.percent_above_outdoor(), 0);
+
+        let double = ECo2::OUTDOOR_AIR * 2;
+        assert!(double.above_outdoor_baseline());
+        assert_eq!(double.ppm_above_outdoor(), 420);
+        assert_eq!(double.percent_above_outdoor(), 100);
+    }
+
+    #[test]
+    fn test_eco2_valid_range() {
+        assert!(!ECo2::from(0).is_in_valid_range());
+        assert!(!ECo2::from(399).is_in_valid_range());
+        assert!(ECo2::from(400).is_in_valid_range());
+        assert!(ECo2::from(65000).is_in_valid_range());
+        assert!(!ECo2::from(65001).is_in_valid_range());
+
+        assert_eq!(ECo2::from(0).clamp_to_valid(), ECo2::MIN_VALID);
+        assert_eq!(ECo2::from(812).clamp_to_valid(), ECo2::from(812));
+        assert_eq!(ECo2::from(u16::MAX).clamp_to_valid(), ECo2::MAX_VALID);
+    }
+
+    #[test]
+    fn test_deltas() {
+        let rising = ECo2::from(612) - ECo2::from(600);
+        assert_eq!(rising.to_string(), "+12 ppm");
+        assert!(rising.is_increasing());
+        assert_eq!(rising.abs(), 12);
+        assert_eq!(rising.signum(), 1);
+
+        let falling = ECo2::from(600) - ECo2::from(605);
+        assert_eq!(falling.to_string(), "-5 ppm");
+        assert!(!falling.is_increasing());
+        assert_eq!(falling.abs(), 5);
+        assert_eq!(falling.signum(), -1);
+
+        let unchanged = ECo2::from(600) - ECo2::from(600);
+        assert!(!unchanged.is_increasing());
+        assert_eq!(unchanged.signum(), 0);
+
+        let falling = Tvoc::from(80) - Tvoc::from(87);
+        assert_eq!(falling, TvocDelta(-7));
+        assert_eq!(falling.to_string(), "-7 ppb");
+        assert!(!falling.is_increasing());
+        assert!((Tvoc::from(87) - Tvoc::from(80)).is_increasing());
+        assert_eq!((Tvoc::from(0) - Tvoc::from(u16::MAX)).abs(), u16::MAX);
+    }
+
+    #[test]
+    fn test_defaults() {
+        assert_eq!(AirQualityIndex::default(), AirQualityIndex::Excellent);
+        assert_eq!(ECo2::default().ppm(), 400);
+        assert!(ECo2::default().is_in_valid_range());
+        let bundle = MeasurementBundle::default();
+        assert_eq!(bundle.aqi.value, AirQualityIndex::Excellent);
+        assert_eq!(bundle.tvoc.value, Tvoc::CLEAN_AIR);
+        assert_eq!(bundle.eco2.value, ECo2::OUTDOOR_BASELINE);
+        assert_eq!(bundle.status.0, 0);
+        assert!(bundle.is_valid() && bundle.eco2.is_valid());
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const ECO2: ECo2 = ECo2::from_ppm(412);
+        const TVOC: Tvoc = Tvoc::from_ppb(87);
+        assert_eq!(ECO2, ECo2::from(412));
+        assert_eq!(TVOC, Tvoc::from(87));
+    }
+
+    #[test]
+    fn test_tvoc() {
+        let low = Tvoc::from(87);
+        let high = Tvoc::from(660);
+        assert!(low < high);
+        assert_eq!(low.max(high), high);
+        assert_eq!(*high - *low, 573);
+        assert_eq!(u16::from(Tvoc::from(Tvoc::MAX)), Tvoc::MAX);
+        assert_eq!(Tvoc::default().ppb(), Tvoc::MIN);
+        assert_eq!(low.to_string(), "87 ppb TVOC");
+    }
+
+    #[test]
+    fn test_tvoc_pollution_levels() {
+        let levels = [
+            (0, true, false, false),
+            (219, true, false, false),
+            (220, false, true, false),
+            (659, false, true, false),
+            (660, false, false, true),
+            (u16::MAX, false, false, true),
+        ];
+        for (ppb, low, moderate, high) in levels {
+            let tvoc = Tvoc::from(ppb);
+            assert_eq!(tvoc.is_low_pollution(), low, "{ppb} ppb");
+            assert_eq!(tvoc.is_moderate_pollution(), moderate, "{ppb} ppb");
+            assert_eq!(tvoc.is_high_pollution(), high, "{ppb} ppb");
+            assert_eq!(tvoc.needs_ventilation(), !low, "{ppb} ppb");
+        }
+    }
+
+    #[test]
+    fn test_tvoc_uba_category() {
+        let bands = [
+            (0, TvocCategory::Excellent),
+            (64, TvocCategory::Excellent),
+            (65, TvocCategory::Good),
+            (219, TvocCategory::Good),
+            (220, TvocCategory::Moderate),
+            (659, TvocCategory::Moderate),
+            (660, TvocCategory::Poor),
+            (2199, TvocCategory::Poor),
+            (2200, TvocCategory::Unhealthy),
+            (u16::MAX, TvocCategory::Unhealthy),
+        ];
+        for (ppb, category) in bands {
+            let tvoc = Tvoc::from(ppb);
+            assert_eq!(tvoc.uba_category(), category, "{ppb} ppb");
+            assert_eq!(
+                AirQualityIndex::from(category),
+                AirQualityIndex::from_tvoc(tvoc)
+            );
+        }
+        assert_eq!(
+            TvocCategory::Excellent.description(),
+            "No hygienic concerns"
+        );
+    }
+
+    #[test]
+    fn test_tvoc_airquality_index() {
+        let expected = [
+            (0, AirQualityIndex::Excellent),
+            (30, AirQualityIndex::Excellent),
+            (64, AirQualityIndex::Excellent),
+            (65, AirQualityIndex::Good),
+            (150, AirQualityIndex::Good),
+            (219, AirQualityIndex::Good),
+            (220, AirQualityIndex::Moderate),
+            (400, AirQualityIndex::Moderate),
+            (659, AirQualityIndex::Moderate),
+            (660, AirQualityIndex::Poor),
+            (1500, AirQualityIndex::Poor),
+            (2199, AirQualityIndex::Poor),
+            (2200, AirQualityIndex::Unhealthy),
+            (10000, AirQualityIndex::Unhealthy),
+            (65000, AirQualityIndex::Unhealthy),
+        ];
+        for (tvoc, aqi) in expected {
+            assert_eq!(AirQualityIndex::try_from(Tvoc::from(tvoc)).unwrap(), aqi);
+            assert_eq!(AirQualityIndex::from_tvoc(Tvoc::from(tvoc)), aqi);
+        }
+
+        assert!(AirQualityIndex::try_from(Tvoc::from(65001)).is_err());
+        assert_eq!(
+            AirQualityIndex::from_tvoc(Tvoc::from(u16::MAX)),
+            AirQualityIndex::Unhealthy
+        );
+    }
+
+    #[cfg(feature = "defmt")]
+    #[test]
+    fn test_defmt_format() {
+        fn assert_format<T: defmt::Format>() {}
+
+        assert_format::<AirQualityIndex>();
+        assert_format::<ECo2>();
+        assert_format::<ECo2Delta>();
+        assert_format::<TvocDelta>();
+        assert_format::<Tvoc>();
+        assert_format::<Status>();
+        assert_format::<Validity>();
+        assert_format::<OperationMode>();
+        assert_format::<InterruptConfig>();
+        assert_format::<PinMode>();
+        assert_format::<crate::InterruptState>();
+        assert_format::<crate::MeasurementBundle>();
+    }
+
+    #[cfg(feature = "ufmt")]
+    #[test]
+    fn test_ufmt() {
+        fn display<T: ufmt::uDisplay>(value: T) -> String {
+            let mut s = String::new();
+            ufmt::uwrite!(s, "{}", value).unwrap();
+            s
+        }
+        fn debug<T: ufmt::uDebug>(value: T) -> String {
+            let mut s = String::new();
+            ufmt::uwrite!(s, "{:?}", value).unwrap();
+            s
+        }
+
+        assert_eq!(display(ECo2::from(412)), "412");
+        assert_eq!(display(Tvoc::from(87)), "87");
+        assert_eq!(display(AirQualityIndex::Moderate), "3");
+        assert_eq!(debug(ECo2::from(412)), "ECo2(412)");
+        assert_eq!(debug(Tvoc::from(87)), "Tvoc(87)");
+        assert_eq!(debug(AirQualityIndex::Moderate), "Moderate");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        use crate::MeasurementBundle;
+
+        let bundle = MeasurementBundle {
+            status: Status(0b10000010),
+            aqi: SensorReading::new(AirQualityIndex::Moderate, Validity::NormalOperation),
+            tvoc: SensorReading::new(Tvoc::from(87), Validity::NormalOperation),
+            eco2: SensorReading::new(ECo2::from(412), Validity::NormalOperation),
+        };
+
+        let json = serde_json::to_string(&bundle.eco2).unwrap();
+        assert_eq!(
+            json,
+            r#"{"value":412,"validity":"NormalOperation","data_age":0}"#
+        );
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed: MeasurementBundle = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.status.0, bundle.status.0);
+        assert_eq!(parsed.aqi, bundle.aqi);
+        assert_eq!(parsed.tvoc, bundle.tvoc);
+        assert_eq!(parsed.eco2, bundle.eco2);
+
+        let mut buffer = [0u8; 32];
+        let bytes = postcard::to_slice(&bundle, &mut buffer).unwrap();
+        let parsed: MeasurementBundle = postcard::from_bytes(bytes).unwrap();
+        assert_eq!(parsed.aqi, bundle.aqi);
+        assert_eq!(parsed.eco2, bundle.eco2);
+
+        let validity: Validity = serde_json::from_str(r#""WarmupPhase""#).unwrap();
+        assert_eq!(validity, Validity::WarmupPhase);
+        assert!(serde_json::from_str::<AirQualityIndex>("6").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_airquality_index() {
+        for index in 1..=5 {
+            let aqi = AirQualityIndex::from_index(index).unwrap();
+
+            let json = serde_json::to_string(&aqi).unwrap();
+            assert_eq!(json, format!("\"{}\"", aqi.label()));
+            assert_eq!(serde_json::from_str::<AirQualityIndex>(&json).unwrap(), aqi);
+            let numeric = serde_json::from_str::<AirQualityIndex>(&index.to_string()).unwrap();
+            assert_eq!(numeric, aqi);
+
+            let mut buffer = [0u8; 1];
+            let bytes = postcard::to_slice(&aqi, &mut buffer).unwrap();
+            assert_eq!(bytes, [index]);
+            assert_eq!(postcard::from_bytes::<AirQualityIndex>(bytes).unwrap(), aqi);
+        }
+        assert!(serde_json::from_str::<AirQualityIndex>("0").is_err());
+        assert!(serde_json::from_str::<AirQualityIndex>("-1").is_err());
+        assert!(serde_json::from_str::<AirQualityIndex>(r#""Great""#).is_err());
+        assert!(postcard::from_bytes::<AirQualityIndex>(&[6]).is_err());
+    }
+
+    #[test]
+    fn test_address() {
+        assert_eq!(u8::from(Address::Primary), 0x52);
+        assert_eq!(u8::from(Address::Secondary), 0x53);
+        assert_eq!(Address::try_from(0x52).unwrap(), Address::Primary);
+        assert_eq!(Address::try_from(0x53).unwrap(), Address::Secondary);
+        assert!(Address::try_from(0x51).is_err());
+    }
+
+    #[test]
+    fn test_firmware_version() {
+        let version = FirmwareVersion::from((5, 4, 6));
+        assert_eq!(version.to_string(), "5.4.6");
+        assert!(version < FirmwareVersion::from((5, 5, 0)));
+        assert!(version < FirmwareVersion::from((6, 0, 0)));
+        assert!(version > FirmwareVersion::from((5, 4, 5)));
+        assert!(version > FirmwareVersion::from((4, 9, 9)));
+        assert!(version.is_at_least(5, 4, 6));
+        assert!(version.is_at_least(5, 3, 9));
+        assert!(!version.is_at_least(5, 4, 7));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_firmware_version_heapless() {
+        let version = FirmwareVersion::from((255, 255, 255));
+        assert_eq!(version.to_heapless_string().as_str(), "255.255.255");
     }
 
     #[test]
@@ -0,0 +1,20 @@
+//! Marker types for the operation mode of the sensor.
+//!
+//! The mode is part of the type of [`Ens160`](crate::Ens160), so operations only available
+//! in a specific mode can't be called in another one.
+
+/// The operation mode is not tracked. All mode transitions are available.
+#[derive(Debug, Clone, Copy)]
+pub struct Unknown;
+
+/// DEEP SLEEP mode (low power standby).
+#[derive(Debug, Clone, Copy)]
+pub struct Sleep;
+
+/// IDLE mode (low-power). Operations with `ENS160_COMMAND_REG` are available.
+#[derive(Debug, Clone, Copy)]
+pub struct Idle;
+
+/// STANDARD gas sensing mode.
+#[derive(Debug, Clone, Copy)]
+pub struct Standard;
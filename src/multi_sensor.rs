@@ -0,0 +1,141 @@
+use crate::error::Error;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::interface::Interface;
+use crate::Ens160;
+#[cfg(any(feature = "blocking", feature = "async"))]
+use crate::{mean_u16, ECo2, MeasurementBundle};
+
+/// `N` sensors sharing one I2C bus, e.g. one at each [`Address`](crate::Address).
+///
+/// Each sensor gets its own clone of the bus, so `I2C` has to be a shareable bus handle like
+/// the devices of `embedded-hal-bus`.
+pub struct MultiSensorBus<I2C, const N: usize> {
+    sensors: [Ens160<I2C>; N],
+}
+
+impl<I2C: Clone, const N: usize> MultiSensorBus<I2C, N> {
+    /// Creates a driver for each of the `addresses`, which have to be distinct.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds if an address occurs more than once.
+    pub fn new(i2c: I2C, addresses: [u8; N]) -> Self {
+        const { assert!(N > 0, "MultiSensorBus needs at least one sensor") };
+        debug_assert!(
+            addresses
+                .iter()
+                .enumerate()
+                .all(|(i, address)| !addresses[i + 1..].contains(address)),
+            "MultiSensorBus addresses have to be distinct"
+        );
+        Self {
+            sensors: addresses.map(|address| Ens160::new(i2c.clone(), address)),
+        }
+    }
+}
+
+impl<I2C, const N: usize> MultiSensorBus<I2C, N> {
+    /// Returns the sensor drivers.
+    pub fn sensors_mut(&mut self) -> &mut [Ens160<I2C>; N] {
+        &mut self.sensors
+    }
+
+    /// Releases the sensor drivers.
+    pub fn release(self) -> [Ens160<I2C>; N] {
+        self.sensors
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<I2C, E, const N: usize> MultiSensorBus<I2C, N>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Reads all measurements of each sensor, see [`Ens160::read_all()`].
+    pub fn read_all_sensors(&mut self) -> [Result<MeasurementBundle, Error<E>>; N] {
+        self.sensors.each_mut().map(|sensor| sensor.read_all())
+    }
+
+    /// Returns the average eCO2 concentration of all sensors.
+    ///
+    /// Fails with the first error if any sensor can't be read.
+    pub fn average_eco2(&mut self) -> Result<ECo2, Error<E>> {
+        let mut sum = 0u32;
+        for sensor in self.sensors.iter_mut() {
+            sum += u32::from(sensor.eco2()?.ppm());
+        }
+        Ok(ECo2::from(
+            mean_u16(sum.into(), N as u64).expect("N is checked to be non-zero in new()"),
+        ))
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I2C, E, const N: usize> MultiSensorBus<I2C, N>
+where
+    I2C: Interface<Error = E>,
+{
+    /// Reads all measurements of each sensor, see [`Ens160::read_all()`].
+    pub async fn read_all_sensors(&mut self) -> [Result<MeasurementBundle, Error<E>>; N] {
+        let mut results = [(); N].map(|_| None);
+        for (sensor, result) in self.sensors.iter_mut().zip(results.iter_mut()) {
+            *result = Some(sensor.read_all().await);
+        }
+        results.map(|result| result.expect("every sensor has been read"))
+    }
+
+    /// Returns the average eCO2 concentration of all sensors.
+    ///
+    /// Fails with the first error if any sensor can't be read.
+    pub async fn average_eco2(&mut self) -> Result<ECo2, Error<E>> {
+        let mut sum = 0u32;
+        for sensor in self.sensors.iter_mut() {
+            sum += u32::from(sensor.eco2().await?.ppm());
+        }
+        Ok(ECo2::from(
+            mean_u16(sum.into(), N as u64).expect("N is checked to be non-zero in new()"),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "blocking"))]
+mod test {
+
+    use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+    use super::MultiSensorBus;
+    use crate::ECo2;
+
+    #[test]
+    fn test_read_all_sensors() {
+        let expectations = [
+            Transaction::write_read(
+                0x52,
+                vec![0x20],
+                vec![0b10000010, 0x01, 0x10, 0x00, 0x90, 0x01],
+            ),
+            Transaction::write_read(
+                0x53,
+                vec![0x20],
+                vec![0b10000010, 0x02, 0x57, 0x00, 0x58, 0x02],
+            ),
+            Transaction::write_read(0x52, vec![0x24], vec![0x90, 0x01]),
+            Transaction::write_read(0x53, vec![0x24], vec![0x58, 0x02]),
+        ];
+        let mut i2c = Mock::new(&expectations);
+        let mut sensors = MultiSensorBus::new(i2c.clone(), [0x52, 0x53]);
+        let [first, second] = sensors.read_all_sensors();
+        assert_eq!(first.unwrap().eco2.value, ECo2::from(400));
+        assert_eq!(second.unwrap().eco2.value, ECo2::from(600));
+        assert_eq!(sensors.average_eco2().unwrap(), ECo2::from(500));
+        i2c.done();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "MultiSensorBus addresses have to be distinct")]
+    fn test_new_rejects_duplicate_addresses() {
+        let i2c = Mock::new(&[]);
+        MultiSensorBus::new(i2c, [0x52, 0x52]);
+    }
+}
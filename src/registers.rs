@@ -1,3 +1,9 @@
+// Part ID reported by the ENS160 in ENS160_PART_ID_REG.
+pub const ENS160_PART_ID: u16 = 0x0160;
+
+// Time in milliseconds the ENS160 needs to become ready after a reset.
+pub const ENS160_RESET_DELAY_MS: u32 = 10;
+
 // ENS160 Register address
 // This 2-byte register contains the part number in little endian of the ENS160.
 pub const ENS160_PART_ID_REG: u8 = 0x00;
@@ -19,19 +25,40 @@ pub const ENS160_DATA_STATUS_REG: u8 = 0x20;
 pub const ENS160_DATA_AQI_REG: u8 = 0x21;
 // This 2-byte register reports the calculated TVOC concentration in ppb.
 pub const ENS160_DATA_TVOC_REG: u8 = 0x22;
+// This 2-byte register reports the calculated ethanol concentration in ppb. It shares its address with
+// ENS160_DATA_TVOC_REG, some firmware versions report the raw ethanol-equivalent reading here.
+pub const ENS160_DATA_ETOH_REG: u8 = 0x22;
 // This 2-byte register reports the calculated equivalent CO2-concentration in ppm, based on the detected VOCs and hydrogen.
 pub const ENS160_DATA_ECO2_REG: u8 = 0x24;
 // This 2-byte register reports the temperature used in its calculations (taken from TEMP_IN, if supplied).
 pub const ENS160_DATA_T_REG: u8 = 0x30;
 // This 2-byte register reports the relative humidity used in its calculations (taken from RH_IN if supplied).
-#[allow(dead_code)]
 pub const ENS160_DATA_RH_REG: u8 = 0x32;
 // This 1-byte register reports the calculated checksum of the previous DATA_ read transaction (of n-bytes).
-#[allow(dead_code)]
 pub const ENS160_DATA_MISR_REG: u8 = 0x38;
 // This 8-byte register is used by several functions for the Host System to pass data to the ENS160.
-#[allow(dead_code)]
 pub const ENS160_GPR_WRITE_REG: u8 = 0x40;
 // This 8-byte register is used by several functions for the ENS160 to pass data to the Host System.
-#[allow(dead_code)]
 pub const ENS160_GPR_READ_REG: u8 = 0x48;
+
+// The individual bytes of ENS160_GPR_WRITE_REG.
+pub const ENS160_GPR_WRITE_REG_0: u8 = 0x40;
+pub const ENS160_GPR_WRITE_REG_1: u8 = 0x41;
+pub const ENS160_GPR_WRITE_REG_2: u8 = 0x42;
+pub const ENS160_GPR_WRITE_REG_3: u8 = 0x43;
+pub const ENS160_GPR_WRITE_REG_4: u8 = 0x44;
+pub const ENS160_GPR_WRITE_REG_5: u8 = 0x45;
+pub const ENS160_GPR_WRITE_REG_6: u8 = 0x46;
+pub const ENS160_GPR_WRITE_REG_7: u8 = 0x47;
+// The individual bytes of ENS160_GPR_READ_REG.
+pub const ENS160_GPR_READ_REG_0: u8 = 0x48;
+pub const ENS160_GPR_READ_REG_1: u8 = 0x49;
+pub const ENS160_GPR_READ_REG_2: u8 = 0x4A;
+pub const ENS160_GPR_READ_REG_3: u8 = 0x4B;
+pub const ENS160_GPR_READ_REG_4: u8 = 0x4C;
+pub const ENS160_GPR_READ_REG_5: u8 = 0x4D;
+pub const ENS160_GPR_READ_REG_6: u8 = 0x4E;
+pub const ENS160_GPR_READ_REG_7: u8 = 0x4F;
+
+// Polynomial x^8 + x^4 + x^3 + x^2 + 1 (x^8 implicit) used to calculate ENS160_DATA_MISR_REG.
+pub const MISR_POLYNOMIAL: u8 = 0x1D;
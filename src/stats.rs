@@ -0,0 +1,157 @@
+use crate::{mean_u16, ECo2, MeasurementBundle, Tvoc};
+
+/// Minimum, maximum and mean of the eCO2 and TVOC concentrations of several measurements.
+///
+/// The sums are kept in `u32`, which can hold up to `u16::MAX` readings of `u16::MAX`. Once
+/// `u16::MAX` measurements are accumulated, further measurements only update the minimum and
+/// maximum.
+#[derive(Debug, Clone)]
+pub struct SensorStats {
+    eco2_min: ECo2,
+    eco2_max: ECo2,
+    eco2_sum: u32,
+    tvoc_min: Tvoc,
+    tvoc_max: Tvoc,
+    tvoc_sum: u32,
+    count: u16,
+    skipped: u16,
+}
+
+impl SensorStats {
+    /// Creates an empty accumulator.
+    pub const fn new() -> Self {
+        Self {
+            eco2_min: ECo2::from_ppm(u16::MAX),
+            eco2_max: ECo2::from_ppm(0),
+            eco2_sum: 0,
+            tvoc_min: Tvoc::from_ppb(u16::MAX),
+            tvoc_max: Tvoc::from_ppb(0),
+            tvoc_sum: 0,
+            count: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Adds the concentrations of a measurement.
+    pub fn update(&mut self, bundle: &MeasurementBundle) {
+        self.eco2_min = self.eco2_min.min(bundle.eco2.value);
+        self.eco2_max = self.eco2_max.max(bundle.eco2.value);
+        self.tvoc_min = self.tvoc_min.min(bundle.tvoc.value);
+        self.tvoc_max = self.tvoc_max.max(bundle.tvoc.value);
+        if self.count < u16::MAX {
+            self.eco2_sum += u32::from(bundle.eco2.ppm());
+            self.tvoc_sum += u32::from(bundle.tvoc.ppb());
+            self.count += 1;
+        }
+    }
+
+    /// Counts a measurement which wasn't added, e.g. because it was invalid.
+    pub fn skip(&mut self) {
+        self.skipped = self.skipped.saturating_add(1);
+    }
+
+    /// Returns the number of measurements the means are calculated from.
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+
+    /// Returns the number of skipped measurements, see [`SensorStats::skip()`].
+    pub fn skipped(&self) -> u16 {
+        self.skipped
+    }
+
+    /// Returns the lowest eCO2 concentration, `None` if no measurement was added.
+    pub fn eco2_min(&self) -> Option<ECo2> {
+        (self.count > 0).then_some(self.eco2_min)
+    }
+
+    /// Returns the highest eCO2 concentration, `None` if no measurement was added.
+    pub fn eco2_max(&self) -> Option<ECo2> {
+        (self.count > 0).then_some(self.eco2_max)
+    }
+
+    /// Returns the mean eCO2 concentration, `None` if no measurement was added.
+    pub fn eco2_mean(&self) -> Option<ECo2> {
+        self.mean(self.eco2_sum).map(ECo2::from)
+    }
+
+    /// Returns the lowest TVOC concentration, `None` if no measurement was added.
+    pub fn tvoc_min(&self) -> Option<Tvoc> {
+        (self.count > 0).then_some(self.tvoc_min)
+    }
+
+    /// Returns the highest TVOC concentration, `None` if no measurement was added.
+    pub fn tvoc_max(&self) -> Option<Tvoc> {
+        (self.count > 0).then_some(self.tvoc_max)
+    }
+
+    /// Returns the mean TVOC concentration, `None` if no measurement was added.
+    pub fn tvoc_mean(&self) -> Option<Tvoc> {
+        self.mean(self.tvoc_sum).map(Tvoc::from)
+    }
+
+    /// Removes all measurements.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    fn mean(&self, sum: u32) -> Option<u16> {
+        mean_u16(sum.into(), self.count.into())
+    }
+}
+
+impl Default for SensorStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::SensorStats;
+    use crate::{bundle, ECo2, Tvoc};
+
+    #[test]
+    fn test_min_max_mean() {
+        let mut stats = SensorStats::new();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.eco2_min(), None);
+        assert_eq!(stats.eco2_mean(), None);
+        assert_eq!(stats.tvoc_max(), None);
+
+        for (eco2, tvoc) in [(600, 20), (400, 0), (800, 100), (401, 11)] {
+            stats.update(&bundle(eco2, tvoc));
+        }
+        assert_eq!(stats.count(), 4);
+        assert_eq!(stats.eco2_min(), Some(ECo2::from(400)));
+        assert_eq!(stats.eco2_max(), Some(ECo2::from(800)));
+        assert_eq!(stats.eco2_mean(), Some(ECo2::from(550)));
+        assert_eq!(stats.tvoc_min(), Some(Tvoc::from(0)));
+        assert_eq!(stats.tvoc_max(), Some(Tvoc::from(100)));
+        assert_eq!(stats.tvoc_mean(), Some(Tvoc::from(32)));
+
+        stats.skip();
+        assert_eq!(stats.skipped(), 1);
+        assert_eq!(stats.count(), 4);
+
+        stats.reset();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.skipped(), 0);
+        assert_eq!(stats.tvoc_mean(), None);
+    }
+
+    #[test]
+    fn test_saturation() {
+        let mut stats = SensorStats::default();
+        for _ in 0..u16::MAX {
+            stats.update(&bundle(u16::MAX, u16::MAX));
+        }
+        stats.update(&bundle(0, 0));
+        assert_eq!(stats.count(), u16::MAX);
+        assert_eq!(stats.eco2_mean(), Some(ECo2::from(u16::MAX)));
+        assert_eq!(stats.tvoc_mean(), Some(Tvoc::from(u16::MAX)));
+        assert_eq!(stats.eco2_min(), Some(ECo2::from(0)));
+        assert_eq!(stats.tvoc_max(), Some(Tvoc::from(u16::MAX)));
+    }
+}
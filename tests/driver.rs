@@ -0,0 +1,112 @@
+#![cfg(feature = "blocking")]
+
+use embedded_hal_mock::eh1::i2c::{Mock, Transaction};
+
+use ens160::{
+    AirQualityIndex, ECo2, Ens160, FirmwareVersion, InterruptConfig, Tvoc, Validity, ENS160_PART_ID,
+};
+
+const ADDRESS: u8 = 0x52;
+
+fn device(expectations: &[Transaction]) -> Ens160<Mock> {
+    Ens160::new(Mock::new(expectations), ADDRESS)
+}
+
+#[test]
+fn test_mode_transitions() {
+    let expectations = [
+        Transaction::write(ADDRESS, vec![0x10, 0xF0]),
+        Transaction::write(ADDRESS, vec![0x10, 0x01]),
+        Transaction::write(ADDRESS, vec![0x10, 0x02]),
+    ];
+    let device = device(&expectations)
+        .reset()
+        .unwrap()
+        .idle()
+        .unwrap()
+        .operational()
+        .unwrap();
+    device.release().done();
+}
+
+#[test]
+fn test_part_id() {
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![0x00],
+        vec![0x60, 0x01],
+    )];
+    let mut device = device(&expectations);
+    assert_eq!(device.part_id().unwrap(), ENS160_PART_ID);
+    device.release().done();
+}
+
+#[test]
+fn test_firmware_version() {
+    let expectations = [
+        Transaction::write(ADDRESS, vec![0x10, 0x01]),
+        Transaction::write(ADDRESS, vec![0x12, 0x0E]),
+        Transaction::write_read(ADDRESS, vec![0x48], vec![5, 4, 6, 0, 0, 0, 0, 0]),
+    ];
+    let mut device = device(&expectations).idle().unwrap();
+    assert_eq!(
+        device.firmware_version().unwrap(),
+        FirmwareVersion::from((5, 4, 6))
+    );
+    device.release().done();
+}
+
+#[test]
+fn test_status() {
+    let expectations = [Transaction::write_read(
+        ADDRESS,
+        vec![0x20],
+        vec![0b10000110],
+    )];
+    let mut device = device(&expectations);
+    let status = device.status().unwrap();
+    assert!(status.running_normally());
+    assert!(!status.error());
+    assert_eq!(status.validity_flag(), Validity::WarmupPhase);
+    assert!(status.data_is_ready());
+    assert!(!status.new_data_in_gpr());
+    device.release().done();
+}
+
+#[test]
+fn test_measurements() {
+    let expectations = [
+        Transaction::write_read(ADDRESS, vec![0x21], vec![0x02]),
+        Transaction::write_read(ADDRESS, vec![0x22], vec![0x57, 0x00]),
+        Transaction::write_read(ADDRESS, vec![0x24], vec![0x9C, 0x01]),
+    ];
+    let mut device = device(&expectations);
+    assert_eq!(device.airquality_index().unwrap(), AirQualityIndex::Good);
+    assert_eq!(device.tvoc().unwrap(), Tvoc::from(87));
+    assert_eq!(device.eco2().unwrap(), ECo2::from(412));
+    device.release().done();
+}
+
+#[test]
+fn test_compensation() {
+    let expectations = [
+        Transaction::write(ADDRESS, vec![0x13, 0x89, 0x4A]),
+        Transaction::write(ADDRESS, vec![0x15, 0x00, 0x64]),
+        Transaction::write_read(ADDRESS, vec![0x30], vec![0x89, 0x4A, 0x00, 0x64]),
+    ];
+    let mut device = device(&expectations);
+    device.set_temp(2500).unwrap();
+    device.set_hum(5000).unwrap();
+    assert_eq!(device.temp_and_hum().unwrap(), (2499, 5000));
+    device.release().done();
+}
+
+#[test]
+fn test_set_interrupt_config() {
+    let expectations = [Transaction::write(ADDRESS, vec![0x11, 0b01100011])];
+    let mut device = device(&expectations);
+    device
+        .set_interrupt_config(InterruptConfig::data_ready_push_pull_active_high())
+        .unwrap();
+    device.release().done();
+}